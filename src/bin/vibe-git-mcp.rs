@@ -1,9 +1,53 @@
-use std::io::{self, BufRead};
-use vibe_git::McpClient;
+use std::{
+    io::{self, BufRead},
+    path::Path,
+    time::Duration,
+};
+use vibe_git::{
+    default_metrics_path, format_elapsed, BranchName, McpClient, PruneOptions, ResetMode,
+    SignatureStatus, StopOptions,
+};
+
+/// Apply `--stay`, `--return` and `--gc` flags on top of `defaults`.
+fn stop_options<'a>(defaults: &StopOptions, flags: impl Iterator<Item = &'a str>) -> StopOptions {
+    let mut options = defaults.clone();
+    for flag in flags {
+        match flag {
+            "--stay" => options.return_to_base = false,
+            "--return" => options.return_to_base = true,
+            "--gc" => options.gc = true,
+            _ => {}
+        }
+    }
+    options
+}
+
+/// Split `start` arguments into the `--orphan` flag, the `--from` ref and the
+/// branch words, in any order. Returns `None` on an unknown `--` flag or a
+/// `--from` without a ref.
+fn start_args<'a>(
+    mut words: impl Iterator<Item = &'a str>,
+) -> Option<(bool, Option<&'a str>, Vec<&'a str>)> {
+    let (mut orphan, mut from_ref, mut branch) = (false, None, Vec::new());
+    while let Some(word) = words.next() {
+        match word {
+            "--orphan" => orphan = true,
+            "--from" => from_ref = Some(words.next()?),
+            flag if flag.starts_with("--") => return None,
+            word => branch.push(word),
+        }
+    }
+    Some((orphan, from_ref, branch))
+}
 
 fn main() {
     let stdin = io::stdin();
     let mut client = McpClient::new();
+    if std::env::var_os("VIBE_METRICS").is_some_and(|value| value == "1") {
+        if let Some(path) = default_metrics_path() {
+            client = client.with_metrics(path);
+        }
+    }
     for line in stdin.lock().lines() {
         let line = match line {
             Ok(l) => l,
@@ -11,21 +55,187 @@ fn main() {
         };
         let mut parts = line.split_whitespace();
         match parts.next() {
-            Some("start") => {
-                if let Some(branch) = parts.next() {
-                    client.start_vibing(branch);
-                    println!("started {branch}");
-                } else {
-                    println!("usage: start <branch>");
+            Some("start") => match start_args(parts) {
+                Some((orphan, from_ref, words)) if !words.is_empty() => {
+                    let branch = BranchName::sanitize(&words.join(" "));
+                    let started = match (orphan, from_ref) {
+                        (true, Some(from_ref)) => {
+                            client.start_orphan_from(branch.clone(), from_ref)
+                        }
+                        (true, None) => client.start_orphan(branch.clone()),
+                        (false, Some(from_ref)) => {
+                            client.start_vibing_from(branch.clone(), from_ref)
+                        }
+                        (false, None) => client.start_vibing(branch.clone()),
+                    };
+                    match started {
+                        Ok(()) => println!("started {branch}"),
+                        Err(err) => println!("error: {err}"),
+                    }
                 }
-            }
+                _ => println!("usage: start [--orphan] [--from <ref>] <branch>"),
+            },
             Some("stop") => {
-                client.stop_vibing();
-                println!("stopped");
+                let options = stop_options(client.stop_defaults(), parts);
+                match client.stop_vibing_with(&options) {
+                    Ok(Some(finished)) if finished.base_missing() => println!(
+                        "stopped; staying on {} because the base branch does not exist",
+                        finished.branch()
+                    ),
+                    Ok(Some(finished)) => match finished.base_worktree() {
+                        Some(worktree) => println!(
+                            "stopped; staying on {} because the base is checked out in {}",
+                            finished.branch(),
+                            worktree.display()
+                        ),
+                        None => println!("stopped"),
+                    },
+                    Ok(None) => println!("stopped"),
+                    Err(err) => println!("error: {err}"),
+                }
             }
+            Some("stop-defaults") => {
+                let options = stop_options(client.stop_defaults(), parts);
+                client.set_stop_defaults(options);
+                println!("stop defaults updated");
+            }
+            Some("stash") => match client.stash() {
+                Ok(true) => println!("stashed"),
+                Ok(false) => println!("nothing to stash"),
+                Err(err) => println!("error: {err}"),
+            },
+            Some("stash-pop") => match client.stash_pop() {
+                Ok(()) => println!("restored"),
+                Err(err) => println!("error: {err}"),
+            },
+            Some("export") => match parts.next() {
+                Some(dir) => {
+                    let squashed = parts.next() == Some("--squash");
+                    match client.export_patches(Path::new(dir), squashed) {
+                        Ok(paths) => {
+                            for path in paths {
+                                println!("{}", path.display());
+                            }
+                        }
+                        Err(err) => println!("error: {err}"),
+                    }
+                }
+                None => println!("usage: export <dir> [--squash]"),
+            },
+            Some("divergence") => match client.divergence(parts.next()) {
+                Ok(divergence) => println!("{divergence}"),
+                Err(err) => println!("error: {err}"),
+            },
+            Some("rewind") => match parts.next().map(str::parse::<usize>) {
+                Some(Ok(count)) => {
+                    let mode = if parts.next() == Some("--hard") {
+                        ResetMode::Hard
+                    } else {
+                        ResetMode::Soft
+                    };
+                    match client.rewind(count, mode) {
+                        Ok(()) => println!("rewound {count} commits"),
+                        Err(err) => println!("error: {err}"),
+                    }
+                }
+                _ => println!("usage: rewind <count> [--hard]"),
+            },
+            Some("suggest") => match client.suggest_commit_message() {
+                Ok(Some(message)) => println!("{message}"),
+                Ok(None) => println!("no session changes yet"),
+                Err(err) => println!("error: {err}"),
+            },
+            Some("hotspots") => match client.hotspots() {
+                Ok(hotspots) if hotspots.is_empty() => println!("no session commits yet"),
+                Ok(hotspots) => {
+                    for hotspot in hotspots {
+                        println!(
+                            "{} ({} commits, {:+} lines)",
+                            hotspot.path,
+                            hotspot.commits,
+                            hotspot.net_lines()
+                        );
+                    }
+                }
+                Err(err) => println!("error: {err}"),
+            },
+            Some("squash-patch") => match client.squash_patch() {
+                Ok(patch) if patch.is_empty() => println!("no session changes yet"),
+                Ok(patch) => print!("{patch}"),
+                Err(err) => println!("error: {err}"),
+            },
+            Some("diff-since") => match parts.next() {
+                Some(commit) => match client.diff_since(commit) {
+                    Ok(diff) if diff.is_empty() => println!("no changes since {commit}"),
+                    Ok(diff) => println!("{diff}"),
+                    Err(err) => println!("error: {err}"),
+                },
+                None => println!("usage: diff-since <commit>"),
+            },
+            Some("impact") => match client.repo_impact() {
+                Ok(impact) if impact.suggests_gc() => {
+                    println!("{impact}; consider running git gc")
+                }
+                Ok(impact) => println!("{impact}"),
+                Err(err) => println!("error: {err}"),
+            },
+            Some("verify") => match client.verify_signatures() {
+                Ok(commits) if commits.is_empty() => println!("no session commits yet"),
+                Ok(commits) => {
+                    for commit in commits {
+                        let status = match commit.status {
+                            SignatureStatus::Verified => "verified",
+                            SignatureStatus::Unsigned => "unsigned",
+                            SignatureStatus::Invalid => "INVALID",
+                        };
+                        println!("{status} {:.12} {}", commit.commit, commit.summary);
+                    }
+                }
+                Err(err) => println!("error: {err}"),
+            },
+            Some("compare") => match (parts.next(), parts.next()) {
+                (Some(from), Some(to)) => {
+                    let include_patch = parts.next() == Some("--patch");
+                    match client.compare_branches(&from.into(), &to.into(), include_patch) {
+                        Ok(comparison) => println!("{comparison}"),
+                        Err(err) => println!("error: {err}"),
+                    }
+                }
+                _ => println!("usage: compare <branch> <branch> [--patch]"),
+            },
+            Some("prune") => match (
+                parts.next(),
+                parts
+                    .next()
+                    .and_then(|days| days.parse::<u64>().ok())
+                    .and_then(|days| days.checked_mul(24 * 60 * 60)),
+            ) {
+                (Some(pattern), Some(seconds)) => {
+                    let flags: Vec<&str> = parts.collect();
+                    let options = PruneOptions {
+                        pattern: pattern.to_string(),
+                        min_age: Duration::from_secs(seconds),
+                        confirm: flags.contains(&"--confirm"),
+                        force: flags.contains(&"--force"),
+                    };
+                    match client.prune_vibe_branches(&options) {
+                        Ok(stale) if stale.is_empty() => println!("no stale branches"),
+                        Ok(stale) => {
+                            for branch in stale {
+                                let action = if branch.deleted { "deleted" } else { "stale" };
+                                let merged = if branch.merged { "merged" } else { "unmerged" };
+                                println!("{action} {} ({merged})", branch.name);
+                            }
+                        }
+                        Err(err) => println!("error: {err}"),
+                    }
+                }
+                _ => println!("usage: prune <pattern> <days> [--confirm] [--force]"),
+            },
             Some("status") => {
                 if let Some(branch) = client.branch() {
-                    println!("vibing on {branch}");
+                    let elapsed = format_elapsed(client.elapsed().unwrap_or_default());
+                    println!("vibing on {branch} (Elapsed: {elapsed})");
                 } else {
                     println!("idle");
                 }
@@ -34,3 +244,23 @@ fn main() {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_flags_parse_in_any_order() {
+        let parse = |line: &'static str| start_args(line.split_whitespace());
+        assert_eq!(
+            parse("--from v1.0 --orphan my snapshots"),
+            Some((true, Some("v1.0"), vec!["my", "snapshots"]))
+        );
+        assert_eq!(
+            parse("fix --orphan bug"),
+            Some((true, None, vec!["fix", "bug"]))
+        );
+        assert_eq!(parse("--form v1.0 branch"), None);
+        assert_eq!(parse("branch --from"), None);
+    }
+}
@@ -0,0 +1,107 @@
+use std::{fmt, io, path::PathBuf};
+
+/// Errors returned by vibe-git operations.
+#[derive(Debug)]
+pub enum VibeError {
+    /// Spawning the git process failed.
+    Io(io::Error),
+    /// A git command exited unsuccessfully.
+    Git { args: Vec<String>, stderr: String },
+    /// The branch name is not a valid git ref name.
+    InvalidBranchName { name: String, reason: &'static str },
+    /// The branch is protected and cannot be vibed on.
+    ProtectedBranch(String),
+    /// The named branch does not exist.
+    UnknownBranch(String),
+    /// The revision does not name a commit.
+    UnknownRevision(String),
+    /// The named remote is not configured in the repository.
+    UnknownRemote(String),
+    /// No base branch could be found to start from.
+    NoBaseBranch,
+    /// The operation requires an active vibing session.
+    NotVibing,
+    /// No stash entry created by vibe-git is available to restore.
+    NoStash,
+    /// The working directory is not inside a git repository.
+    NotARepository,
+    /// A `.git` directory exists but git cannot read the repository.
+    CorruptRepository(String),
+    /// The repository is in the middle of a merge, rebase or similar.
+    OperationInProgress(&'static str),
+    /// The commit is not one the session made.
+    NotASessionCommit(String),
+    /// A rewind asked for more commits than the session has made.
+    RewindPastBase { requested: usize, available: usize },
+    /// The branch has commits not merged into its base and was kept.
+    UnmergedBranch(String),
+    /// The base branch is checked out in another worktree, so the session
+    /// stayed on its vibe branch.
+    BaseCheckedOutElsewhere { base: String, worktree: PathBuf },
+}
+
+impl fmt::Display for VibeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to run git: {err}"),
+            Self::Git { args, stderr } => write!(f, "git {} failed: {stderr}", args.join(" ")),
+            Self::InvalidBranchName { name, reason } => {
+                write!(f, "invalid branch name {name:?}: {reason}")
+            }
+            Self::ProtectedBranch(branch) => {
+                write!(
+                    f,
+                    "branch {branch} is protected; vibe on a new branch instead"
+                )
+            }
+            Self::UnknownBranch(branch) => write!(f, "branch {branch} does not exist"),
+            Self::UnknownRevision(revision) => write!(f, "{revision} is not a known commit"),
+            Self::UnknownRemote(remote) => write!(f, "remote {remote} is not configured"),
+            Self::NoBaseBranch => f.write_str("could not determine a base branch"),
+            Self::NotVibing => f.write_str("no active vibing session"),
+            Self::NoStash => f.write_str("no vibe-git stash to restore"),
+            Self::NotARepository => f.write_str("not a git repository"),
+            Self::CorruptRepository(detail) => write!(f, "git repository is corrupt: {detail}"),
+            Self::OperationInProgress(operation) => write!(
+                f,
+                "repository has a {operation} in progress; finish or abort it before vibing"
+            ),
+            Self::NotASessionCommit(commit) => {
+                write!(f, "{commit} is not a commit of this session")
+            }
+            Self::RewindPastBase {
+                requested,
+                available,
+            } => write!(
+                f,
+                "cannot rewind {requested} commits; the session only has {available}"
+            ),
+            Self::UnmergedBranch(branch) => {
+                write!(
+                    f,
+                    "branch {branch} is not merged into its base; pass force to delete it"
+                )
+            }
+            Self::BaseCheckedOutElsewhere { base, worktree } => write!(
+                f,
+                "base branch {base} is checked out in worktree {}",
+                worktree.display()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VibeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for VibeError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
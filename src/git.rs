@@ -0,0 +1,236 @@
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{BranchName, VibeError};
+
+/// Environment variable naming the git executable, e.g. a policy wrapper.
+pub const GIT_BINARY_ENV: &str = "VIBE_GIT_BINARY";
+
+/// The git executable to invoke, `git` unless [`GIT_BINARY_ENV`] is set.
+pub(crate) fn binary() -> OsString {
+    std::env::var_os(GIT_BINARY_ENV)
+        .filter(|binary| !binary.is_empty())
+        .unwrap_or_else(|| "git".into())
+}
+
+/// Build a command for the configured git executable, run inside `repo`.
+pub(crate) fn command(repo: &Path) -> Command {
+    let mut command = Command::new(binary());
+    command.current_dir(repo);
+    command
+}
+
+/// Run git in `repo` with `args` and return its stdout without trailing
+/// whitespace.
+pub(crate) fn run(repo: &Path, args: &[&str]) -> Result<String, VibeError> {
+    let stdout = run_raw(repo, args)?;
+    Ok(String::from_utf8_lossy(&stdout).trim_end().to_string())
+}
+
+/// Run git in `repo` with `args` and return its stdout byte for byte.
+///
+/// Use this for patches, where trailing whitespace is significant.
+pub(crate) fn run_raw(repo: &Path, args: &[&str]) -> Result<Vec<u8>, VibeError> {
+    let output = command(repo).args(args).output()?;
+    if !output.status.success() {
+        return Err(VibeError::Git {
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(output.stdout)
+}
+
+/// Check that `repo` is a usable repository.
+///
+/// An unborn HEAD, as in a repository with no commits yet, is accepted. A
+/// repository is only reported as corrupt when git fails to read it even
+/// when pointed at it directly; other failures, such as git refusing a
+/// repository owned by another user, are returned as they are.
+pub(crate) fn validate_repository(repo: &Path) -> Result<(), VibeError> {
+    if let Err(err) = run(repo, &["rev-parse", "--git-dir"]) {
+        if !matches!(err, VibeError::Git { .. }) {
+            return Err(err);
+        }
+        let Some(git_dir) = repo
+            .canonicalize()?
+            .ancestors()
+            .map(|dir| dir.join(".git"))
+            .find(|git_dir| git_dir.exists())
+        else {
+            return Err(VibeError::NotARepository);
+        };
+        let mut explicit = OsString::from("--git-dir=");
+        explicit.push(&git_dir);
+        let read = command(repo)
+            .arg(explicit)
+            .args(["rev-parse", "--git-dir"])
+            .output()?;
+        if !read.status.success() {
+            let stderr = String::from_utf8_lossy(&read.stderr);
+            return Err(VibeError::CorruptRepository(stderr.trim().to_string()));
+        }
+        return Err(err);
+    }
+    if let Ok(head) = run(repo, &["symbolic-ref", "-q", "HEAD"]) {
+        if run(repo, &["rev-parse", "-q", "--verify", &head]).is_err() {
+            return Ok(());
+        }
+    }
+    run(repo, &["rev-parse", "-q", "--verify", "HEAD^{commit}"])
+        .map_err(|_| VibeError::CorruptRepository("HEAD does not point to a commit".into()))?;
+    Ok(())
+}
+
+/// Whether the local branch `name` exists.
+pub(crate) fn branch_exists(repo: &Path, name: &str) -> bool {
+    run(
+        repo,
+        &["rev-parse", "-q", "--verify", &format!("refs/heads/{name}")],
+    )
+    .is_ok()
+}
+
+/// Fail with [`VibeError::UnknownBranch`] unless the local branch exists.
+pub(crate) fn ensure_branch(repo: &Path, name: &str) -> Result<(), VibeError> {
+    if branch_exists(repo, name) {
+        Ok(())
+    } else {
+        Err(VibeError::UnknownBranch(name.to_string()))
+    }
+}
+
+/// Remote whose default branch is preferred as a session's base.
+const DEFAULT_REMOTE: &str = "origin";
+
+/// Return the default branch of `remote` from `refs/remotes/<remote>/HEAD`.
+///
+/// With `query`, a missing ref is first filled in by asking the remote.
+pub(crate) fn remote_default_branch(repo: &Path, remote: &str, query: bool) -> Option<String> {
+    let head_ref = format!("refs/remotes/{remote}/HEAD");
+    let cached = || run(repo, &["symbolic-ref", "--short", "-q", &head_ref]).ok();
+    let head = cached().or_else(|| {
+        query
+            .then(|| run(repo, &["remote", "set-head", remote, "--auto"]).ok())
+            .flatten()
+            .and_then(|_| cached())
+    })?;
+    head.strip_prefix(&format!("{remote}/")).map(str::to_string)
+}
+
+/// Pick the branch a session starts from and returns to.
+///
+/// Prefers the default branch of `origin` when a local branch of that name
+/// exists. Then tries each of `candidates` in order, then
+/// `init.defaultBranch`, and finally the branch currently checked out
+/// (which may still be unborn).
+pub(crate) fn detect_base_branch(
+    repo: &Path,
+    candidates: &[BranchName],
+    query_remote: bool,
+) -> Result<String, VibeError> {
+    let remote = remote_default_branch(repo, DEFAULT_REMOTE, query_remote);
+    let configured = run(repo, &["config", "--get", "init.defaultBranch"]).ok();
+    let candidates = candidates.iter().map(BranchName::as_str);
+    for candidate in remote
+        .as_deref()
+        .into_iter()
+        .chain(candidates)
+        .chain(configured.as_deref())
+    {
+        if branch_exists(repo, candidate) {
+            return Ok(candidate.to_string());
+        }
+    }
+    run(repo, &["symbolic-ref", "--short", "-q", "HEAD"]).map_err(|_| VibeError::NoBaseBranch)
+}
+
+/// Parse `-z --numstat` output into `(insertions, deletions, path)` entries.
+///
+/// With `-z` paths are printed verbatim instead of quoted, e.g. when they
+/// contain non-ASCII characters. Renames report their new path. Binary files
+/// report `-` for both counts and are counted as zero.
+pub(crate) fn parse_numstat(output: &str) -> impl Iterator<Item = (u64, u64, &str)> {
+    let mut fields = output.split('\0');
+    std::iter::from_fn(move || loop {
+        let mut counts = fields.next()?.splitn(3, '\t');
+        let (Some(insertions), Some(deletions), Some(path)) =
+            (counts.next(), counts.next(), counts.next())
+        else {
+            continue;
+        };
+        // A rename leaves the path empty and follows with the old and new path.
+        let path = if path.is_empty() {
+            fields.next()?;
+            fields.next()?
+        } else {
+            path
+        };
+        return Some((
+            insertions.parse().unwrap_or(0),
+            deletions.parse().unwrap_or(0),
+            path,
+        ));
+    })
+}
+
+/// Return the commit id of the newest stash entry, if any.
+pub(crate) fn stash_top(repo: &Path) -> Option<String> {
+    run(repo, &["rev-parse", "-q", "--verify", "refs/stash"]).ok()
+}
+
+/// List the worktrees of `repo` that have a branch checked out, as
+/// `(path, branch)` pairs.
+pub(crate) fn worktree_branches(repo: &Path) -> Result<Vec<(PathBuf, String)>, VibeError> {
+    let listing = run(repo, &["worktree", "list", "--porcelain"])?;
+    Ok(listing
+        .split("\n\n")
+        .filter_map(|entry| {
+            let mut path = None;
+            let mut branch = None;
+            for line in entry.lines() {
+                if let Some(worktree) = line.strip_prefix("worktree ") {
+                    path = Some(PathBuf::from(worktree));
+                } else if let Some(name) = line.strip_prefix("branch refs/heads/") {
+                    branch = Some(name.to_string());
+                }
+            }
+            Some((path?, branch?))
+        })
+        .collect())
+}
+
+/// Return the path of another worktree that has `branch` checked out.
+pub(crate) fn worktree_for_branch(repo: &Path, branch: &str) -> Result<Option<PathBuf>, VibeError> {
+    let current = PathBuf::from(run(repo, &["rev-parse", "--show-toplevel"])?);
+    let current = current.canonicalize().unwrap_or(current);
+    Ok(worktree_branches(repo)?
+        .into_iter()
+        .find(|(path, name)| {
+            name == branch && path.canonicalize().unwrap_or_else(|_| path.clone()) != current
+        })
+        .map(|(path, _)| path))
+}
+
+/// Return the name of an unfinished git operation, if the repository is in one.
+pub(crate) fn operation_in_progress(repo: &Path) -> Result<Option<&'static str>, VibeError> {
+    const MARKERS: [(&str, &str); 6] = [
+        ("MERGE_HEAD", "merge"),
+        ("rebase-merge", "rebase"),
+        ("rebase-apply", "rebase"),
+        ("CHERRY_PICK_HEAD", "cherry-pick"),
+        ("REVERT_HEAD", "revert"),
+        ("BISECT_LOG", "bisect"),
+    ];
+    for (marker, operation) in MARKERS {
+        let path = run(repo, &["rev-parse", "--git-path", marker])?;
+        // The path is relative to the directory git ran in.
+        if repo.join(path).exists() {
+            return Ok(Some(operation));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,47 @@
+use std::{collections::HashMap, path::Path};
+
+use crate::{git, VibeError};
+
+/// How often a file changed across a session's commits.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Hotspot {
+    pub path: String,
+    pub commits: usize,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+impl Hotspot {
+    /// Net lines added (negative when the file shrank).
+    pub fn net_lines(&self) -> i64 {
+        self.insertions as i64 - self.deletions as i64
+    }
+}
+
+/// Rank files changed in `range` of `repo`, most frequently modified first.
+pub(crate) fn hotspots(repo: &Path, range: &str) -> Result<Vec<Hotspot>, VibeError> {
+    let log = git::run(
+        repo,
+        &["log", "-z", "--numstat", "--no-renames", "--format=", range],
+    )?;
+    let mut by_path: HashMap<&str, Hotspot> = HashMap::new();
+    for (insertions, deletions, path) in git::parse_numstat(&log) {
+        let entry = by_path.entry(path).or_insert_with(|| Hotspot {
+            path: path.to_string(),
+            commits: 0,
+            insertions: 0,
+            deletions: 0,
+        });
+        entry.commits += 1;
+        entry.insertions += insertions;
+        entry.deletions += deletions;
+    }
+    let mut ranked: Vec<Hotspot> = by_path.into_values().collect();
+    ranked.sort_by(|a, b| {
+        b.commits
+            .cmp(&a.commits)
+            .then((b.insertions + b.deletions).cmp(&(a.insertions + a.deletions)))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(ranked)
+}
@@ -0,0 +1,74 @@
+use std::{fmt, path::Path};
+
+use crate::{git, VibeError};
+
+/// Loose objects git itself considers worth a `git gc --auto`.
+const GC_LOOSE_OBJECTS: i64 = 6700;
+
+/// Object count and on-disk size of a repository's object store.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct ObjectStats {
+    loose: i64,
+    objects: i64,
+    size_kib: i64,
+}
+
+/// Growth of the object store since a session started.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RepoImpact {
+    pub objects_added: i64,
+    pub kib_added: i64,
+    /// Loose objects in the repository now, whether or not the session
+    /// created them.
+    pub loose_objects: i64,
+}
+
+impl RepoImpact {
+    /// Whether enough loose objects piled up that `git gc` is worth running.
+    pub fn suggests_gc(&self) -> bool {
+        self.loose_objects >= GC_LOOSE_OBJECTS
+    }
+}
+
+impl fmt::Display for RepoImpact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} objects, {} KiB added",
+            self.objects_added, self.kib_added
+        )
+    }
+}
+
+impl ObjectStats {
+    /// The change from `baseline` to these stats.
+    pub(crate) fn since(&self, baseline: &ObjectStats) -> RepoImpact {
+        RepoImpact {
+            objects_added: self.objects - baseline.objects,
+            kib_added: self.size_kib - baseline.size_kib,
+            loose_objects: self.loose,
+        }
+    }
+}
+
+/// Read `git count-objects -v` for `repo`.
+pub(crate) fn object_stats(repo: &Path) -> Result<ObjectStats, VibeError> {
+    let output = git::run(repo, &["count-objects", "-v"])?;
+    let mut stats = ObjectStats::default();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(": ") else {
+            continue;
+        };
+        let value: i64 = value.trim().parse().unwrap_or(0);
+        match key {
+            "count" => {
+                stats.loose = value;
+                stats.objects += value;
+            }
+            "in-pack" => stats.objects += value,
+            "size" | "size-pack" => stats.size_kib += value,
+            _ => {}
+        }
+    }
+    Ok(stats)
+}
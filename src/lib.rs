@@ -1,14 +1,116 @@
-use std::{fmt, marker::PhantomData, process::Command};
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BranchName(String);
 
 impl BranchName {
+    /// Longest branch name accepted by [`try_new`](Self::try_new).
+    pub const MAX_LEN: usize = 100;
+
+    /// Validate `input` strictly against git's ref naming rules.
+    pub fn try_new(input: &str) -> Result<Self, VibeError> {
+        match invalid_reason(input) {
+            Some(reason) => Err(VibeError::InvalidBranchName {
+                name: input.to_string(),
+                reason,
+            }),
+            None => Ok(Self(input.to_string())),
+        }
+    }
+
+    /// Turn arbitrary `input` into a valid branch name.
+    ///
+    /// Illegal characters become `-`, forbidden sequences are collapsed or
+    /// stripped and the result is truncated to [`MAX_LEN`](Self::MAX_LEN).
+    /// Input with nothing usable left, or that sanitizes to `@` or `HEAD`,
+    /// falls back to `vibe`.
+    pub fn sanitize(input: &str) -> Self {
+        let replaced: String = input
+            .replace("@{", "-")
+            .chars()
+            .map(|c| if is_illegal_char(c) { '-' } else { c })
+            .take(Self::MAX_LEN)
+            .collect();
+        let mut collapsed = String::with_capacity(replaced.len());
+        for c in replaced.chars() {
+            if !(c == '-' && collapsed.ends_with('-')) {
+                collapsed.push(c);
+            }
+        }
+        let components: Vec<String> = collapsed
+            .split('/')
+            .map(|component| {
+                let mut component = component.to_string();
+                while component.contains("..") {
+                    component = component.replace("..", ".");
+                }
+                let mut component = component.trim_start_matches(['.', '-']);
+                // Stripping one suffix can expose the other, as in "a.lock.".
+                loop {
+                    let trimmed = component.trim_end_matches('.');
+                    let trimmed = trimmed.strip_suffix(".lock").unwrap_or(trimmed);
+                    if trimmed == component {
+                        break;
+                    }
+                    component = trimmed;
+                }
+                component.to_string()
+            })
+            .filter(|component| !component.is_empty())
+            .collect();
+        let name = components.join("/");
+        if name.is_empty() || name == "@" || name == "HEAD" {
+            Self("vibe".to_string())
+        } else {
+            Self(name)
+        }
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
 }
 
+fn is_illegal_char(c: char) -> bool {
+    c.is_whitespace() || c.is_control() || matches!(c, '~' | '^' | ':' | '?' | '*' | '[' | '\\')
+}
+
+/// Explain why `name` is not a valid branch name, mirroring `git check-ref-format`.
+fn invalid_reason(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("name is empty")
+    } else if name.chars().count() > BranchName::MAX_LEN {
+        Some("name is too long")
+    } else if name == "@" {
+        Some("name cannot be `@`")
+    } else if name == "HEAD" {
+        Some("name cannot be `HEAD`")
+    } else if name.starts_with('-') {
+        Some("name cannot start with `-`")
+    } else if name.chars().any(is_illegal_char) {
+        Some("name contains whitespace, a control character or one of ~^:?*[\\")
+    } else if name.contains("..") {
+        Some("name cannot contain `..`")
+    } else if name.contains("@{") {
+        Some("name cannot contain `@{`")
+    } else if name.ends_with('.') {
+        Some("name cannot end with `.`")
+    } else if name.split('/').any(|component| component.is_empty()) {
+        Some("name cannot start or end with `/` or contain `//`")
+    } else if name
+        .split('/')
+        .any(|component| component.starts_with('.') || component.ends_with(".lock"))
+    {
+        Some("path components cannot start with `.` or end with `.lock`")
+    } else {
+        None
+    }
+}
+
 impl From<&str> for BranchName {
     fn from(value: &str) -> Self {
         Self(value.to_string())
@@ -33,68 +135,596 @@ impl fmt::Display for BranchName {
     }
 }
 
+/// Format a duration for status output, e.g. `12m 30s`.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes}m {seconds}s")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+mod error;
+mod git;
+mod hotspots;
+mod impact;
 mod mcp;
+mod metrics;
+mod prune;
+mod signatures;
+mod suggest;
 
-pub use mcp::McpClient;
+pub use error::VibeError;
+pub use git::GIT_BINARY_ENV;
+pub use hotspots::Hotspot;
+pub use impact::RepoImpact;
+pub use mcp::{McpClient, StopOptions};
+pub use metrics::{append_summary, default_metrics_path, SessionSummary};
+pub use prune::{PruneOptions, StaleBranch};
+pub use signatures::{CommitSignature, SignatureStatus};
+
+/// Branches tried in order when detecting a session's base branch.
+pub const DEFAULT_BASE_CANDIDATES: [&str; 2] = ["main", "master"];
+
+/// Branches a session refuses to vibe on unless configured otherwise.
+pub const DEFAULT_PROTECTED_BRANCHES: [&str; 2] = ["main", "master"];
+
+/// How far a vibe branch has diverged from its base.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Divergence {
+    /// The ref compared against, e.g. `main` or `origin/main`.
+    pub base: String,
+    pub merge_base: String,
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} commits ahead, {} behind {}",
+            self.ahead, self.behind, self.base
+        )
+    }
+}
+
+/// What a rewind does with the changes of the dropped commits.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ResetMode {
+    /// Keep the changes staged in the working tree.
+    #[default]
+    Soft,
+    /// Discard the changes entirely.
+    Hard,
+}
+
+/// State of a session before it has started, holding the options that
+/// [`VibeSession::start`] consumes.
+#[derive(Debug)]
+pub struct Idle {
+    options: StartOptions,
+}
+
+/// State of an active vibing session.
+#[derive(Debug)]
+pub struct Vibing {
+    stashes: Vec<String>,
+    objects_at_start: impact::ObjectStats,
+}
 
-/// Marker type for the session before it has started.
-pub struct Idle;
+/// State of a completed session, recording why it stayed on its branch.
+#[derive(Debug)]
+pub struct Finished {
+    base_worktree: Option<PathBuf>,
+    base_missing: bool,
+}
 
-/// Marker type for an active vibing session.
-pub struct Vibing;
+impl Finished {
+    /// Nothing kept the session from finishing where it was asked to.
+    fn new() -> Self {
+        Self {
+            base_worktree: None,
+            base_missing: false,
+        }
+    }
+}
 
-/// Marker type for a completed session.
-pub struct Finished;
+/// How an idle session picks its base and creates its branch.
+#[derive(Debug)]
+struct StartOptions {
+    base_candidates: Vec<BranchName>,
+    protected_branches: Vec<BranchName>,
+    query_remote_head: bool,
+    orphan: bool,
+    start_point: Option<String>,
+}
 
 /// A session that transitions through compile-time states.
+#[derive(Debug)]
 pub struct VibeSession<State> {
+    repo: PathBuf,
     branch: BranchName,
-    state: PhantomData<State>,
+    base: Option<BranchName>,
+    started_at: SystemTime,
+    state: State,
+}
+
+impl<State> VibeSession<State> {
+    /// Move the session's shared data into the `Next` state.
+    fn transition<Next>(self, state: Next) -> VibeSession<Next> {
+        VibeSession {
+            repo: self.repo,
+            branch: self.branch,
+            base: self.base,
+            started_at: self.started_at,
+            state,
+        }
+    }
 }
 
 impl VibeSession<Idle> {
-    /// Create a new session in the idle state.
+    /// Create a new session in the idle state for the current directory.
     pub fn new(branch: impl Into<BranchName>) -> Self {
+        Self::in_repo(".", branch)
+    }
+
+    /// Create a new idle session whose git commands run inside `repo`.
+    pub fn in_repo(repo: impl Into<PathBuf>, branch: impl Into<BranchName>) -> Self {
         Self {
+            repo: repo.into(),
             branch: branch.into(),
-            state: PhantomData,
+            base: None,
+            started_at: SystemTime::now(),
+            state: Idle {
+                options: StartOptions {
+                    base_candidates: DEFAULT_BASE_CANDIDATES.map(BranchName::from).to_vec(),
+                    protected_branches: DEFAULT_PROTECTED_BRANCHES.map(BranchName::from).to_vec(),
+                    query_remote_head: false,
+                    orphan: false,
+                    start_point: None,
+                },
+            },
         }
     }
 
-    /// Start vibing, transitioning to the `Vibing` state.
-    pub fn start(self) -> VibeSession<Vibing> {
-        let status = Command::new("git")
-            .args(["checkout", "-b", self.branch.as_ref()])
-            .status()
-            .expect("failed to run git checkout");
-        assert!(status.success(), "git checkout failed");
+    /// Use `base` instead of detecting the branch to start from.
+    pub fn with_base(mut self, base: impl Into<BranchName>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
 
-        VibeSession {
-            branch: self.branch,
-            state: PhantomData,
+    /// Replace the ordered list of branches tried when detecting the base.
+    pub fn with_base_candidates<I, B>(mut self, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Into<BranchName>,
+    {
+        self.state.options.base_candidates = candidates.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Replace the branches the session refuses to vibe on.
+    pub fn with_protected_branches<I, B>(mut self, protected: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Into<BranchName>,
+    {
+        self.state.options.protected_branches = protected.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Ask `origin` for its default branch when none is cached locally.
+    ///
+    /// The answer is stored as `refs/remotes/origin/HEAD`, so the remote is
+    /// only queried once.
+    pub fn with_remote_head_query(mut self, query: bool) -> Self {
+        self.state.options.query_remote_head = query;
+        self
+    }
+
+    /// Create the vibe branch at `start_point`, e.g. a tag or commit id,
+    /// instead of at the base branch.
+    ///
+    /// The base is still resolved as usual and is where finishing returns.
+    pub fn with_start_point(mut self, start_point: impl Into<String>) -> Self {
+        self.state.options.start_point = Some(start_point.into());
+        self
+    }
+
+    /// Create the vibe branch as an orphan with no history, e.g. for
+    /// publishing build snapshots.
+    ///
+    /// The base is still resolved and checked out again on finish. As with
+    /// `git checkout --orphan`, the base's files stay in the index until
+    /// removed.
+    pub fn with_orphan(mut self, orphan: bool) -> Self {
+        self.state.options.orphan = orphan;
+        self
+    }
+
+    /// Start vibing, transitioning to the `Vibing` state.
+    ///
+    /// Fails with a distinct error when the branch is
+    /// [protected](Self::with_protected_branches), the directory is not a
+    /// repository or the repository is corrupt. Also refuses to start while a merge, rebase
+    /// or similar operation is unfinished, since branching would bury the
+    /// half-done state.
+    ///
+    /// Without an explicit [`with_base`](Self::with_base), the base branch is
+    /// the default branch of `origin`, the first existing
+    /// [base candidate](Self::with_base_candidates), the configured
+    /// `init.defaultBranch` or the current branch, in that order.
+    pub fn start(mut self) -> Result<VibeSession<Vibing>, VibeError> {
+        let options = &self.state.options;
+        BranchName::try_new(self.branch.as_str())?;
+        if options.protected_branches.contains(&self.branch) {
+            return Err(VibeError::ProtectedBranch(self.branch.to_string()));
+        }
+        // An unborn HEAD is fine: the new branch is created unborn too and its
+        // first commit becomes the root commit.
+        git::validate_repository(&self.repo)?;
+        if let Some(operation) = git::operation_in_progress(&self.repo)? {
+            return Err(VibeError::OperationInProgress(operation));
         }
+        let base = match self.base.take() {
+            Some(base) => {
+                git::ensure_branch(&self.repo, base.as_str())?;
+                base
+            }
+            None => git::detect_base_branch(
+                &self.repo,
+                &options.base_candidates,
+                options.query_remote_head,
+            )?
+            .into(),
+        };
+        let create = if options.orphan { "--orphan" } else { "-b" };
+        let mut checkout = vec!["checkout", create, self.branch.as_str()];
+        let start_point = match &options.start_point {
+            Some(start_point) => Some(
+                git::run(
+                    &self.repo,
+                    &[
+                        "rev-parse",
+                        "-q",
+                        "--verify",
+                        &format!("{start_point}^{{commit}}"),
+                    ],
+                )
+                .map_err(|_| VibeError::UnknownRevision(start_point.clone()))?,
+            ),
+            // An unborn base has nothing to start from, so branch off HEAD.
+            None if git::branch_exists(&self.repo, base.as_str()) => Some(base.to_string()),
+            None => None,
+        };
+        checkout.extend(start_point.as_deref());
+        git::run(&self.repo, &checkout)?;
+
+        let objects_at_start = impact::object_stats(&self.repo)?;
+        self.base = Some(base);
+        self.started_at = SystemTime::now();
+        Ok(self.transition(Vibing {
+            stashes: Vec::new(),
+            objects_at_start,
+        }))
     }
 }
 
 impl VibeSession<Vibing> {
     /// Finish vibing, transitioning to the `Finished` state.
+    ///
+    /// A base branch checked out in another worktree cannot be checked out
+    /// here too, so the session then stays on its vibe branch and records
+    /// that worktree in [`VibeSession::<Finished>::base_worktree`]. It also
+    /// stays when the base does not exist, e.g. because the repository had
+    /// no commits when vibing started; see
+    /// [`VibeSession::<Finished>::base_missing`].
     pub fn finish(self) -> VibeSession<Finished> {
-        let status = Command::new("git")
-            .args(["checkout", "main"])
-            .status()
-            .expect("failed to checkout main");
-        assert!(status.success(), "git checkout main failed");
+        match self.return_to_base() {
+            Ok(state) => self.transition(state),
+            Err(err) => panic!("failed to check out base branch {}: {err}", self.base()),
+        }
+    }
 
-        VibeSession {
-            branch: self.branch,
-            state: PhantomData,
+    /// Finish vibing without leaving the vibe branch.
+    pub fn finish_in_place(self) -> VibeSession<Finished> {
+        self.transition(Finished::new())
+    }
+
+    /// Finish vibing and delete the branch after returning to the base.
+    ///
+    /// The branch is only deleted when it is merged into the base or `force`
+    /// is set. On any failure the session is handed back with the error,
+    /// still vibing on its branch, so it can be finished another way:
+    /// [`VibeError::UnmergedBranch`] when the branch is not merged,
+    /// [`VibeError::UnknownBranch`] when the base does not exist,
+    /// [`VibeError::BaseCheckedOutElsewhere`] when another worktree has the
+    /// base checked out and [`VibeError::Git`] when the checkout fails, e.g.
+    /// because of conflicting uncommitted changes.
+    #[allow(clippy::result_large_err)]
+    pub fn finish_and_delete(
+        self,
+        force: bool,
+    ) -> Result<VibeSession<Finished>, (Self, VibeError)> {
+        match self.delete_branch(force) {
+            Ok(()) => Ok(self.transition(Finished::new())),
+            Err(err) => Err((self, err)),
         }
     }
 
+    /// Check out the base and delete the vibe branch, leaving the session
+    /// where it was when any step fails.
+    fn delete_branch(&self, force: bool) -> Result<(), VibeError> {
+        let (base, branch) = (self.base().as_str(), self.branch.as_str());
+        git::ensure_branch(&self.repo, base)?;
+        if let Some(worktree) = git::worktree_for_branch(&self.repo, base)? {
+            return Err(VibeError::BaseCheckedOutElsewhere {
+                base: base.to_string(),
+                worktree,
+            });
+        }
+        let merged = git::run(
+            &self.repo,
+            &["branch", "--merged", base, "--format=%(refname:short)"],
+        )?;
+        if !force && !merged.lines().any(|name| name == branch) {
+            return Err(VibeError::UnmergedBranch(branch.to_string()));
+        }
+        git::run(&self.repo, &["checkout", base])?;
+        if let Err(err) = git::run(&self.repo, &["branch", "-D", branch]) {
+            // Go back to the branch so the session is left as it was.
+            let _ = git::run(&self.repo, &["checkout", branch]);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Check out the base branch, unless it does not exist or another
+    /// worktree has it checked out, which is recorded in the returned state.
+    fn return_to_base(&self) -> Result<Finished, VibeError> {
+        let base = self.base().as_str();
+        if !git::branch_exists(&self.repo, base) {
+            return Ok(Finished {
+                base_worktree: None,
+                base_missing: true,
+            });
+        }
+        let base_worktree = git::worktree_for_branch(&self.repo, base).ok().flatten();
+        if base_worktree.is_none() {
+            git::run(&self.repo, &["checkout", base])?;
+        }
+        Ok(Finished {
+            base_worktree,
+            base_missing: false,
+        })
+    }
+
     /// Access the active branch name.
     pub fn branch(&self) -> &BranchName {
         &self.branch
     }
+
+    /// Access the branch the session started from and returns to.
+    pub fn base(&self) -> &BranchName {
+        self.base
+            .as_ref()
+            .expect("base branch is resolved when vibing starts")
+    }
+
+    /// When the session started vibing.
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    /// Time spent vibing so far.
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed().unwrap_or_default()
+    }
+
+    /// Write the session's commits on top of the base as patch files in `dir`.
+    ///
+    /// By default each commit becomes a `git format-patch` file suitable for
+    /// `git am`. With `squashed` the whole session is written as a single
+    /// diff for `git apply`. Returns the written paths in order.
+    pub fn export_patches(&self, dir: &Path, squashed: bool) -> Result<Vec<PathBuf>, VibeError> {
+        fs::create_dir_all(dir)?;
+        if squashed {
+            let diff = git::run_raw(
+                &self.repo,
+                &[
+                    "diff",
+                    "--binary",
+                    &format!("{}...{}", self.base(), self.branch),
+                ],
+            )?;
+            let name = format!("{}.patch", self.branch.as_str().replace('/', "-"));
+            let path = dir.join(name);
+            fs::write(&path, diff)?;
+            return Ok(vec![path]);
+        }
+        // format-patch runs inside the repository, so resolve `dir` first.
+        let output_dir = std::path::absolute(dir)?;
+        let output_dir = output_dir.to_string_lossy();
+        let range = format!("{}..{}", self.base(), self.branch);
+        let written = git::run(&self.repo, &["format-patch", "-o", &output_dir, &range])?;
+        Ok(written.lines().map(PathBuf::from).collect())
+    }
+
+    /// Count commits ahead of and behind the base branch.
+    ///
+    /// With `fetch_remote`, the base is fetched from that remote first and the
+    /// comparison is made against its remote-tracking branch. The remote must
+    /// be one listed by `git remote`; anything else is
+    /// [`VibeError::UnknownRemote`].
+    pub fn divergence(&self, fetch_remote: Option<&str>) -> Result<Divergence, VibeError> {
+        let base = match fetch_remote {
+            Some(remote) => {
+                // A name like `--upload-pack=...` would be taken as an option.
+                let configured = git::run(&self.repo, &["remote"])?;
+                if remote.starts_with('-') || !configured.lines().any(|name| name == remote) {
+                    return Err(VibeError::UnknownRemote(remote.to_string()));
+                }
+                // Spell out the refspec so the tracking branch is updated even
+                // when the remote's configured fetch refspec does not cover it.
+                let base = self.base();
+                let refspec = format!("+refs/heads/{base}:refs/remotes/{remote}/{base}");
+                git::run(
+                    &self.repo,
+                    &["fetch", "-q", "--end-of-options", remote, &refspec],
+                )?;
+                format!("{remote}/{base}")
+            }
+            None => self.base().to_string(),
+        };
+        let branch = self.branch.as_str();
+        let merge_base = git::run(&self.repo, &["merge-base", &base, branch])?;
+        let counts = git::run(
+            &self.repo,
+            &[
+                "rev-list",
+                "--left-right",
+                "--count",
+                &format!("{base}...{branch}"),
+            ],
+        )?;
+        let mut counts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        let behind = counts.next().unwrap_or(0);
+        let ahead = counts.next().unwrap_or(0);
+        Ok(Divergence {
+            base,
+            merge_base,
+            ahead,
+            behind,
+        })
+    }
+
+    /// Rank the files changed by the session's commits, most edited first.
+    pub fn hotspots(&self) -> Result<Vec<Hotspot>, VibeError> {
+        hotspots::hotspots(&self.repo, &format!("{}..{}", self.base(), self.branch))
+    }
+
+    /// The session's cumulative change as a single patch, without committing.
+    ///
+    /// Diffs the point where the session left its base against the working
+    /// tree, so uncommitted edits to tracked files are included and the
+    /// patch applies cleanly onto that base. Untracked files are not.
+    pub fn squash_patch(&self) -> Result<String, VibeError> {
+        let fork_point = git::run(&self.repo, &["merge-base", self.base().as_str(), "HEAD"])?;
+        let diff = git::run_raw(&self.repo, &["diff", "--binary", &fork_point, "--"])?;
+        Ok(String::from_utf8_lossy(&diff).into_owned())
+    }
+
+    /// Diff the working tree against `commit`, one of the session's commits.
+    ///
+    /// Shows everything changed since that checkpoint, committed or not.
+    /// Untracked files are not included.
+    pub fn diff_since(&self, commit: &str) -> Result<String, VibeError> {
+        let not_in_session = || VibeError::NotASessionCommit(commit.to_string());
+        let id = git::run(
+            &self.repo,
+            &[
+                "rev-parse",
+                "-q",
+                "--verify",
+                &format!("{commit}^{{commit}}"),
+            ],
+        )
+        .map_err(|_| not_in_session())?;
+        let range = format!("{}..{}", self.base(), self.branch);
+        let session_commits = git::run(&self.repo, &["rev-list", &range])?;
+        if !session_commits.lines().any(|line| line == id) {
+            return Err(not_in_session());
+        }
+        let diff = git::run_raw(&self.repo, &["diff", &id, "--"])?;
+        Ok(String::from_utf8_lossy(&diff).into_owned())
+    }
+
+    /// Report how much the object store has grown since the session started.
+    pub fn repo_impact(&self) -> Result<RepoImpact, VibeError> {
+        Ok(impact::object_stats(&self.repo)?.since(&self.state.objects_at_start))
+    }
+
+    /// Check the signature of each session commit, oldest first.
+    pub fn verify_signatures(&self) -> Result<Vec<CommitSignature>, VibeError> {
+        signatures::verify_signatures(&self.repo, &format!("{}..{}", self.base(), self.branch))
+    }
+
+    /// Drop the last `count` session commits, keeping earlier session work.
+    ///
+    /// Refuses to move past the point where the session left its base.
+    pub fn rewind(&mut self, count: usize, mode: ResetMode) -> Result<(), VibeError> {
+        let range = format!("{}..{}", self.base(), self.branch);
+        // The reset walks first parents, so count the same way: commits a
+        // merge brought in are not steps back towards the base.
+        let available = git::run(
+            &self.repo,
+            &["rev-list", "--first-parent", "--count", &range],
+        )?
+        .parse()
+        .unwrap_or(0);
+        if count > available {
+            return Err(VibeError::RewindPastBase {
+                requested: count,
+                available,
+            });
+        }
+        if count == 0 {
+            return Ok(());
+        }
+        let mode = match mode {
+            ResetMode::Soft => "--soft",
+            ResetMode::Hard => "--hard",
+        };
+        git::run(&self.repo, &["reset", "-q", mode, &format!("HEAD~{count}")])?;
+        Ok(())
+    }
+
+    /// Suggest a commit message summarizing the session's committed changes.
+    ///
+    /// Returns `None` when the session has no changes yet.
+    pub fn suggest_commit_message(&self) -> Result<Option<String>, VibeError> {
+        suggest::suggest_commit_message(&self.repo, self.base().as_str(), self.branch.as_str())
+    }
+
+    /// Stash all working tree changes, including untracked files.
+    ///
+    /// Returns `false` when there was nothing to stash.
+    pub fn stash(&mut self) -> Result<bool, VibeError> {
+        let before = git::stash_top(&self.repo);
+        let message = format!("vibe-git: {}", self.branch);
+        git::run(
+            &self.repo,
+            &["stash", "push", "--include-untracked", "-m", &message],
+        )?;
+        match git::stash_top(&self.repo) {
+            Some(top) if Some(&top) != before.as_ref() => {
+                self.state.stashes.push(top);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Restore the most recent stash created by [`stash`](Self::stash).
+    pub fn stash_pop(&mut self) -> Result<(), VibeError> {
+        let id = self.state.stashes.pop().ok_or(VibeError::NoStash)?;
+        let entries = git::run(&self.repo, &["stash", "list", "--format=%H"])?;
+        let index = entries
+            .lines()
+            .position(|entry| entry == id)
+            .ok_or(VibeError::NoStash)?;
+        let entry = format!("stash@{{{index}}}");
+        if let Err(err) = git::run(&self.repo, &["stash", "pop", "--index", &entry]) {
+            self.state.stashes.push(id);
+            return Err(err);
+        }
+        Ok(())
+    }
 }
 
 impl VibeSession<Finished> {
@@ -102,6 +732,18 @@ impl VibeSession<Finished> {
     pub fn branch(&self) -> &BranchName {
         &self.branch
     }
+
+    /// The worktree holding the base branch when finishing had to stay on
+    /// the vibe branch because of it.
+    pub fn base_worktree(&self) -> Option<&Path> {
+        self.state.base_worktree.as_deref()
+    }
+
+    /// Whether finishing stayed on the vibe branch because the base branch
+    /// did not exist, e.g. because it still had no commits.
+    pub fn base_missing(&self) -> bool {
+        self.state.base_missing
+    }
 }
 
 #[cfg(test)]
@@ -134,9 +776,61 @@ mod tests {
             .unwrap();
 
         let idle = VibeSession::<Idle>::new("feature-branch");
-        let vibing = idle.start();
+        let vibing = idle.start().unwrap();
         assert_eq!(vibing.branch().as_ref(), "feature-branch");
         let finished = vibing.finish();
         assert_eq!(finished.branch().as_ref(), "feature-branch");
     }
+
+    #[test]
+    fn branch_name_validation_and_sanitizing() {
+        assert!(BranchName::try_new("feature/login").is_ok());
+        for invalid in [
+            "",
+            "@",
+            "HEAD",
+            "-leading",
+            "has space",
+            "tilde~1",
+            "caret^",
+            "colon:name",
+            "a..b",
+            "trailing/",
+            "/leading",
+            "double//slash",
+            "ends.",
+            "refs.lock",
+            ".hidden",
+            "at@{1}",
+            &"x".repeat(BranchName::MAX_LEN + 1),
+        ] {
+            assert!(
+                BranchName::try_new(invalid).is_err(),
+                "{invalid:?} accepted"
+            );
+        }
+
+        for (input, expected) in [
+            ("my feature", "my-feature"),
+            ("fix: the ~bug^ [now]?", "fix-the-bug-now]-"),
+            ("a..b", "a.b"),
+            ("feature/", "feature"),
+            ("//a//b//", "a/b"),
+            ("-.hidden/ref.lock", "hidden/ref"),
+            ("version@{1}", "version-1}"),
+            ("ends...", "ends"),
+            ("a.lock.", "a"),
+            ("foo.lock..", "foo"),
+            ("x.lock.lock", "x"),
+            ("~~~", "vibe"),
+            ("@", "vibe"),
+            ("HEAD", "vibe"),
+        ] {
+            let sanitized = BranchName::sanitize(input);
+            assert_eq!(sanitized.as_str(), expected, "sanitizing {input:?}");
+            assert!(BranchName::try_new(sanitized.as_str()).is_ok());
+        }
+        let long = BranchName::sanitize(&"y".repeat(300));
+        assert_eq!(long.as_str().len(), BranchName::MAX_LEN);
+    }
 }
@@ -1,35 +1,361 @@
-use crate::{BranchName, Idle, VibeSession, Vibing};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    append_summary, git, prune, BranchName, CommitSignature, Divergence, Finished, Hotspot, Idle,
+    PruneOptions, RepoImpact, ResetMode, SessionSummary, StaleBranch, VibeError, VibeSession,
+    Vibing, DEFAULT_BASE_CANDIDATES, DEFAULT_PROTECTED_BRANCHES,
+};
+
+/// Options controlling how [`McpClient::stop_vibing_with`] ends a session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StopOptions {
+    /// Check out the base branch after stopping. When `false` the working
+    /// tree stays on the vibe branch.
+    pub return_to_base: bool,
+    /// Run `git gc --auto` afterwards to reclaim space from the session's
+    /// objects. git detaches the collection itself, and failures are ignored.
+    pub gc: bool,
+}
+
+impl Default for StopOptions {
+    fn default() -> Self {
+        Self {
+            return_to_base: true,
+            gc: false,
+        }
+    }
+}
 
 /// Simple client API for driving a vibe session.
 pub struct McpClient {
     session: Option<VibeSession<Vibing>>,
+    repo: PathBuf,
+    base: Option<BranchName>,
+    base_candidates: Option<Vec<BranchName>>,
+    protected_branches: Option<Vec<BranchName>>,
+    query_remote_head: bool,
+    metrics: Option<PathBuf>,
+    stop_defaults: StopOptions,
 }
 
 impl McpClient {
     /// Create a new client with no active session.
     pub fn new() -> Self {
-        Self { session: None }
+        Self {
+            session: None,
+            repo: PathBuf::from("."),
+            base: None,
+            base_candidates: None,
+            protected_branches: None,
+            query_remote_head: false,
+            metrics: None,
+            stop_defaults: StopOptions::default(),
+        }
+    }
+
+    /// Run every git command inside `repo` instead of the current directory.
+    pub fn with_repo(mut self, repo: impl Into<PathBuf>) -> Self {
+        self.repo = repo.into();
+        self
+    }
+
+    /// Start sessions from `base` instead of detecting the base branch.
+    pub fn with_base(mut self, base: impl Into<BranchName>) -> Self {
+        self.base = Some(base.into());
+        self
+    }
+
+    /// Try `candidates` in order when detecting the base branch.
+    pub fn with_base_candidates<I, B>(mut self, candidates: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Into<BranchName>,
+    {
+        self.base_candidates = Some(candidates.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Refuse to start sessions on any of `protected`.
+    pub fn with_protected_branches<I, B>(mut self, protected: I) -> Self
+    where
+        I: IntoIterator<Item = B>,
+        B: Into<BranchName>,
+    {
+        self.protected_branches = Some(protected.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Ask `origin` for its default branch when none is cached locally.
+    pub fn with_remote_head_query(mut self, query: bool) -> Self {
+        self.query_remote_head = query;
+        self
+    }
+
+    /// Opt in to appending a [`SessionSummary`] to `path` on every stop.
+    pub fn with_metrics(mut self, path: impl Into<PathBuf>) -> Self {
+        self.metrics = Some(path.into());
+        self
     }
 
     /// Start vibing on the given branch if not already active.
-    pub fn start_vibing(&mut self, branch: impl Into<BranchName>) {
+    pub fn start_vibing(&mut self, branch: impl Into<BranchName>) -> Result<(), VibeError> {
+        self.start(branch.into(), false, None)
+    }
+
+    /// Start vibing on a branch created at `from_ref`, such as a tag or commit,
+    /// instead of at the base branch, if not already active.
+    pub fn start_vibing_from(
+        &mut self,
+        branch: impl Into<BranchName>,
+        from_ref: &str,
+    ) -> Result<(), VibeError> {
+        self.start(branch.into(), false, Some(from_ref))
+    }
+
+    /// Start vibing on a new orphan branch with no history, if not already
+    /// active.
+    pub fn start_orphan(&mut self, branch: impl Into<BranchName>) -> Result<(), VibeError> {
+        self.start(branch.into(), true, None)
+    }
+
+    /// Start vibing on a new orphan branch whose index and working tree come
+    /// from `from_ref` instead of the base branch, if not already active.
+    pub fn start_orphan_from(
+        &mut self,
+        branch: impl Into<BranchName>,
+        from_ref: &str,
+    ) -> Result<(), VibeError> {
+        self.start(branch.into(), true, Some(from_ref))
+    }
+
+    fn start(
+        &mut self,
+        branch: BranchName,
+        orphan: bool,
+        from_ref: Option<&str>,
+    ) -> Result<(), VibeError> {
         if self.session.is_none() {
-            let idle = VibeSession::<Idle>::new(branch);
-            self.session = Some(idle.start());
+            let mut idle = VibeSession::<Idle>::in_repo(&self.repo, branch)
+                .with_remote_head_query(self.query_remote_head)
+                .with_orphan(orphan);
+            if let Some(base) = &self.base {
+                idle = idle.with_base(base.clone());
+            }
+            if let Some(candidates) = &self.base_candidates {
+                idle = idle.with_base_candidates(candidates.iter().cloned());
+            }
+            if let Some(protected) = &self.protected_branches {
+                idle = idle.with_protected_branches(protected.iter().cloned());
+            }
+            if let Some(from_ref) = from_ref {
+                idle = idle.with_start_point(from_ref);
+            }
+            self.session = Some(idle.start()?);
         }
+        Ok(())
+    }
+
+    /// Use `options` for every later [`stop_vibing`](Self::stop_vibing).
+    pub fn set_stop_defaults(&mut self, options: StopOptions) {
+        self.stop_defaults = options;
+    }
+
+    /// Return the options [`stop_vibing`](Self::stop_vibing) applies.
+    pub fn stop_defaults(&self) -> &StopOptions {
+        &self.stop_defaults
     }
 
-    /// Stop the current vibing session if one is active.
-    pub fn stop_vibing(&mut self) {
-        if let Some(vibing) = self.session.take() {
-            let _finished = vibing.finish();
+    /// Stop the current vibing session, if any, using the stop defaults.
+    pub fn stop_vibing(&mut self) -> Result<Option<VibeSession<Finished>>, VibeError> {
+        let options = self.stop_defaults.clone();
+        self.stop_vibing_with(&options)
+    }
+
+    /// Stop the current vibing session, if any, according to `options`.
+    ///
+    /// Returns the finished session, which reports whether the base branch
+    /// could be checked out. When the checkout fails, e.g. because of
+    /// conflicting uncommitted changes, the error is returned and the session
+    /// stays active.
+    pub fn stop_vibing_with(
+        &mut self,
+        options: &StopOptions,
+    ) -> Result<Option<VibeSession<Finished>>, VibeError> {
+        let Some(vibing) = self.session.take() else {
+            return Ok(None);
+        };
+        let state = if options.return_to_base {
+            match vibing.return_to_base() {
+                Ok(state) => state,
+                Err(err) => {
+                    self.session = Some(vibing);
+                    return Err(err);
+                }
+            }
+        } else {
+            Finished::new()
+        };
+        if let Some(path) = &self.metrics {
+            // Metrics are best effort and must never fail the stop.
+            if let Ok(summary) = SessionSummary::from_session(&vibing) {
+                let _ = append_summary(path, &summary);
+            }
+        }
+        let finished = vibing.transition(state);
+        if options.gc {
+            // Housekeeping is best effort, like metrics.
+            let _ = git::run(&self.repo, &["gc", "--auto", "--quiet"]);
+        }
+        Ok(Some(finished))
+    }
+
+    /// Stash the working tree changes of the active session.
+    ///
+    /// Returns `false` when there was nothing to stash.
+    pub fn stash(&mut self) -> Result<bool, VibeError> {
+        self.session.as_mut().ok_or(VibeError::NotVibing)?.stash()
+    }
+
+    /// Restore the most recent stash made with [`stash`](Self::stash).
+    pub fn stash_pop(&mut self) -> Result<(), VibeError> {
+        self.session
+            .as_mut()
+            .ok_or(VibeError::NotVibing)?
+            .stash_pop()
+    }
+
+    /// Export the active session's commits as patch files in `dir`.
+    pub fn export_patches(&self, dir: &Path, squashed: bool) -> Result<Vec<PathBuf>, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .export_patches(dir, squashed)
+    }
+
+    /// Report how far the active session has diverged from its base.
+    pub fn divergence(&self, fetch_remote: Option<&str>) -> Result<Divergence, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .divergence(fetch_remote)
+    }
+
+    /// Rank the files the active session changed most.
+    pub fn hotspots(&self) -> Result<Vec<Hotspot>, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .hotspots()
+    }
+
+    /// Return the active session's changes, committed or not, as one patch.
+    pub fn squash_patch(&self) -> Result<String, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .squash_patch()
+    }
+
+    /// Diff the working tree against one of the active session's commits.
+    pub fn diff_since(&self, commit: &str) -> Result<String, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .diff_since(commit)
+    }
+
+    /// Report the object store growth caused by the active session.
+    pub fn repo_impact(&self) -> Result<RepoImpact, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .repo_impact()
+    }
+
+    /// Check the signatures of the active session's commits.
+    pub fn verify_signatures(&self) -> Result<Vec<CommitSignature>, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .verify_signatures()
+    }
+
+    /// Drop the last `count` commits of the active session.
+    pub fn rewind(&mut self, count: usize, mode: ResetMode) -> Result<(), VibeError> {
+        self.session
+            .as_mut()
+            .ok_or(VibeError::NotVibing)?
+            .rewind(count, mode)
+    }
+
+    /// Suggest a commit message for the active session's changes.
+    pub fn suggest_commit_message(&self) -> Result<Option<String>, VibeError> {
+        self.session
+            .as_ref()
+            .ok_or(VibeError::NotVibing)?
+            .suggest_commit_message()
+    }
+
+    /// Compare two branches, returning a diffstat and optionally the full diff.
+    ///
+    /// This is read-only and does not need an active session.
+    pub fn compare_branches(
+        &self,
+        from: &BranchName,
+        to: &BranchName,
+        include_patch: bool,
+    ) -> Result<String, VibeError> {
+        git::ensure_branch(&self.repo, from.as_str())?;
+        git::ensure_branch(&self.repo, to.as_str())?;
+        let (from, to) = (from.as_str(), to.as_str());
+        let mut comparison = git::run(&self.repo, &["diff", "--stat", from, to, "--"])?;
+        if include_patch {
+            comparison.push_str("\n\n");
+            let patch = git::run_raw(&self.repo, &["diff", from, to, "--"])?;
+            comparison.push_str(&String::from_utf8_lossy(&patch));
         }
+        Ok(comparison)
+    }
+
+    /// List branches matching `options.pattern` whose last commit is older
+    /// than `options.min_age`, deleting them when `options.confirm` is set.
+    ///
+    /// Only branches merged into the base are deleted unless `options.force`
+    /// is also set. The base, branches checked out in any worktree and
+    /// protected branches are skipped. This does not need an active session.
+    pub fn prune_vibe_branches(
+        &self,
+        options: &PruneOptions,
+    ) -> Result<Vec<StaleBranch>, VibeError> {
+        let base = match (&self.session, &self.base) {
+            (Some(session), _) => session.base().to_string(),
+            (None, Some(base)) => {
+                git::ensure_branch(&self.repo, base.as_str())?;
+                base.to_string()
+            }
+            (None, None) => {
+                let defaults = DEFAULT_BASE_CANDIDATES.map(BranchName::from);
+                let candidates = self.base_candidates.as_deref().unwrap_or(&defaults);
+                git::detect_base_branch(&self.repo, candidates, self.query_remote_head)?
+            }
+        };
+        let defaults = DEFAULT_PROTECTED_BRANCHES.map(BranchName::from);
+        let protected = self.protected_branches.as_deref().unwrap_or(&defaults);
+        prune::prune_branches(&self.repo, &base, protected, options)
     }
 
     /// Return the active branch name, if any.
     pub fn branch(&self) -> Option<&BranchName> {
         self.session.as_ref().map(|s| s.branch())
     }
+
+    /// Return how long the active session has been running, if any.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.session.as_ref().map(|s| s.elapsed())
+    }
 }
 
 impl Default for McpClient {
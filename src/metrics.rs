@@ -0,0 +1,102 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{git, VibeError, VibeSession, Vibing};
+
+/// Summary of a finished session, recorded as one JSON line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionSummary {
+    pub branch: String,
+    pub started_at: SystemTime,
+    pub stopped_at: SystemTime,
+    pub commit_count: u64,
+    pub insertions: u64,
+    pub deletions: u64,
+}
+
+impl SessionSummary {
+    /// Summarize `session` against its base, stamping the stop time as now.
+    pub(crate) fn from_session(session: &VibeSession<Vibing>) -> Result<Self, VibeError> {
+        let range = format!("{}..{}", session.base(), session.branch);
+        let commit_count = git::run(&session.repo, &["rev-list", "--count", &range])?
+            .parse()
+            .unwrap_or(0);
+        let numstat = git::run(
+            &session.repo,
+            &[
+                "diff",
+                "-z",
+                "--numstat",
+                &format!("{}...{}", session.base(), session.branch),
+            ],
+        )?;
+        let (mut insertions, mut deletions) = (0, 0);
+        for (added, removed, _) in git::parse_numstat(&numstat) {
+            insertions += added;
+            deletions += removed;
+        }
+        Ok(Self {
+            branch: session.branch.to_string(),
+            started_at: session.started_at,
+            stopped_at: SystemTime::now(),
+            commit_count,
+            insertions,
+            deletions,
+        })
+    }
+
+    /// Serialize the summary as a single JSON object without a newline.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"branch\":{},\"started_at\":{},\"stopped_at\":{},\"commit_count\":{},\"insertions\":{},\"deletions\":{}}}",
+            json_string(&self.branch),
+            unix_seconds(self.started_at),
+            unix_seconds(self.stopped_at),
+            self.commit_count,
+            self.insertions,
+            self.deletions,
+        )
+    }
+}
+
+/// Default metrics location, `~/.vibe-git/metrics.jsonl`.
+pub fn default_metrics_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| Path::new(&home).join(".vibe-git/metrics.jsonl"))
+}
+
+/// Append `summary` as a JSON line to `path`, creating parent directories.
+pub fn append_summary(path: &Path, summary: &SessionSummary) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", summary.to_json())
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
@@ -0,0 +1,90 @@
+use std::{
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{git, BranchName, VibeError};
+
+/// Which branches [`McpClient::prune_vibe_branches`](crate::McpClient::prune_vibe_branches)
+/// considers and whether it deletes them.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PruneOptions {
+    /// `git for-each-ref` pattern under `refs/heads/`, e.g. `vibe-*`.
+    pub pattern: String,
+    /// Only branches whose last commit is at least this old are stale.
+    pub min_age: Duration,
+    /// Delete stale branches merged into the base. When `false` they are
+    /// only listed.
+    pub confirm: bool,
+    /// With `confirm`, also delete stale branches that are not merged.
+    pub force: bool,
+}
+
+/// A branch old enough to prune.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaleBranch {
+    pub name: BranchName,
+    pub last_commit: SystemTime,
+    pub merged: bool,
+    pub deleted: bool,
+}
+
+/// Find, and with `options.confirm` delete, stale branches in `repo`.
+///
+/// The base, branches checked out in any worktree and `protected` branches
+/// are never touched.
+pub(crate) fn prune_branches(
+    repo: &Path,
+    base: &str,
+    protected: &[BranchName],
+    options: &PruneOptions,
+) -> Result<Vec<StaleBranch>, VibeError> {
+    let branches = git::run(
+        repo,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)%09%(committerdate:unix)",
+            &format!("refs/heads/{}", options.pattern),
+        ],
+    )?;
+    let merged = git::run(
+        repo,
+        &["branch", "--merged", base, "--format=%(refname:short)"],
+    )?;
+    // Branches checked out here or in a linked worktree cannot be deleted.
+    let checked_out: Vec<String> = git::worktree_branches(repo)?
+        .into_iter()
+        .map(|(_, branch)| branch)
+        .collect();
+    let cutoff = SystemTime::now()
+        .checked_sub(options.min_age)
+        .unwrap_or(UNIX_EPOCH);
+
+    let mut stale = Vec::new();
+    for line in branches.lines() {
+        let Some((name, timestamp)) = line.split_once('\t') else {
+            continue;
+        };
+        let last_commit = UNIX_EPOCH + Duration::from_secs(timestamp.parse().unwrap_or(0));
+        let name = BranchName::from(name);
+        if last_commit > cutoff
+            || name.as_str() == base
+            || checked_out.iter().any(|branch| branch == name.as_str())
+            || protected.contains(&name)
+        {
+            continue;
+        }
+        let merged = merged.lines().any(|branch| branch == name.as_str());
+        let deleted = options.confirm && (merged || options.force);
+        if deleted {
+            git::run(repo, &["branch", "-D", name.as_str()])?;
+        }
+        stale.push(StaleBranch {
+            name,
+            last_commit,
+            merged,
+            deleted,
+        });
+    }
+    Ok(stale)
+}
@@ -0,0 +1,49 @@
+use std::path::Path;
+
+use crate::{git, VibeError};
+
+/// Outcome of checking one commit's signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SignatureStatus {
+    /// The signature is good.
+    Verified,
+    /// The commit carries no signature.
+    Unsigned,
+    /// The signature is bad, expired, revoked or could not be checked.
+    Invalid,
+}
+
+/// Signature status of a single session commit.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitSignature {
+    pub commit: String,
+    pub summary: String,
+    pub status: SignatureStatus,
+}
+
+/// Check the signature of every commit in `range` of `repo`, oldest first.
+pub(crate) fn verify_signatures(
+    repo: &Path,
+    range: &str,
+) -> Result<Vec<CommitSignature>, VibeError> {
+    let log = git::run(repo, &["log", "--reverse", "--format=%H %G? %s", range])?;
+    Ok(log
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ' ');
+            let commit = fields.next()?.to_string();
+            let status = match fields.next()? {
+                // "U" is a good signature from a key of unknown trust.
+                "G" | "U" => SignatureStatus::Verified,
+                "N" => SignatureStatus::Unsigned,
+                _ => SignatureStatus::Invalid,
+            };
+            let summary = fields.next().unwrap_or_default().to_string();
+            Some(CommitSignature {
+                commit,
+                summary,
+                status,
+            })
+        })
+        .collect())
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use crate::{git, hotspots, Hotspot, VibeError};
+
+/// Suggest a commit message for the changes between `base` and `branch`.
+///
+/// Returns `None` when the branch has no changes relative to its base.
+pub(crate) fn suggest_commit_message(
+    repo: &Path,
+    base: &str,
+    branch: &str,
+) -> Result<Option<String>, VibeError> {
+    let statuses = git::run(
+        repo,
+        &[
+            "diff",
+            "-z",
+            "--name-status",
+            "--no-renames",
+            &format!("{base}...{branch}"),
+        ],
+    )?;
+    // With `-z` each status and path is its own field and paths are unquoted.
+    let mut fields = statuses.split('\0');
+    let statuses: Vec<(char, &str)> = std::iter::from_fn(|| {
+        let status = fields.next()?.chars().next()?;
+        Some((status, fields.next()?))
+    })
+    .collect();
+    let hotspots = hotspots::hotspots(repo, &format!("{base}..{branch}"))?;
+    Ok(compose(&statuses, &hotspots))
+}
+
+/// Build "<Verb> <module>: N files changed" plus one bullet per hotspot.
+fn compose(statuses: &[(char, &str)], hotspots: &[Hotspot]) -> Option<String> {
+    let (_, first) = statuses.first()?;
+    let count = |kind| {
+        statuses
+            .iter()
+            .filter(|(status, _)| *status == kind)
+            .count()
+    };
+    let verb = if count('A') * 2 > statuses.len() {
+        "Add"
+    } else if count('D') * 2 > statuses.len() {
+        "Remove"
+    } else {
+        "Update"
+    };
+
+    let module = if statuses.len() == 1 {
+        first.to_string()
+    } else {
+        let mut common = Path::new(first).parent().unwrap_or(Path::new(""));
+        for (_, path) in statuses {
+            while !Path::new(path).starts_with(common) {
+                common = common.parent().unwrap_or(Path::new(""));
+            }
+        }
+        match common.to_str() {
+            Some(dir) if !dir.is_empty() => dir.to_string(),
+            _ => {
+                let top = hotspots
+                    .first()
+                    .map_or(*first, |hotspot| hotspot.path.as_str());
+                let top = Path::new(top).components().next();
+                top.map_or_else(String::new, |c| {
+                    c.as_os_str().to_string_lossy().into_owned()
+                })
+            }
+        }
+    };
+
+    let files = if statuses.len() == 1 { "file" } else { "files" };
+    let mut message = format!("{verb} {module}: {} {files} changed", statuses.len());
+    if !hotspots.is_empty() {
+        message.push('\n');
+        for hotspot in hotspots {
+            message.push_str(&format!(
+                "\n- {} (+{}/-{})",
+                hotspot.path, hotspot.insertions, hotspot.deletions
+            ));
+        }
+    }
+    Some(message)
+}
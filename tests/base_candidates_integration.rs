@@ -0,0 +1,22 @@
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn first_existing_base_candidate_is_selected() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+    git(repo, &["branch", "-m", "develop"]);
+    git(repo, &["checkout", "-q", "-b", "trunk"]);
+
+    let mut client = McpClient::new()
+        .with_repo(repo)
+        .with_base_candidates(["main", "develop", "trunk"]);
+    client.start_vibing("candidate-branch").unwrap();
+    client.stop_vibing().unwrap();
+
+    assert_eq!(git(repo, &["branch", "--show-current"]), "develop");
+}
@@ -0,0 +1,28 @@
+use tempfile::tempdir;
+use vibe_git::{Idle, VibeSession};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn init_default_branch_is_used_as_base() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+    git(repo, &["branch", "-m", "devel"]);
+    git(repo, &["config", "init.defaultBranch", "devel"]);
+    git(repo, &["checkout", "-q", "-b", "elsewhere"]);
+
+    let vibing = VibeSession::<Idle>::in_repo(repo, "vibe-branch")
+        .start()
+        .unwrap();
+    assert_eq!(vibing.base().as_ref(), "devel");
+    vibing.finish();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "devel");
+
+    let explicit = VibeSession::<Idle>::in_repo(repo, "explicit-branch")
+        .with_base("elsewhere")
+        .start()
+        .unwrap();
+    assert_eq!(explicit.base().as_ref(), "elsewhere");
+}
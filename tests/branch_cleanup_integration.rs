@@ -0,0 +1,79 @@
+use std::{fs, path::Path};
+use tempfile::tempdir;
+use vibe_git::{Idle, VibeError, VibeSession};
+
+mod common;
+use common::{git, git_command, init_empty_repo, init_repo};
+
+fn branch_exists(repo: &Path, name: &str) -> bool {
+    git_command(repo)
+        .args(["rev-parse", "--verify", "-q", &format!("refs/heads/{name}")])
+        .output()
+        .unwrap()
+        .status
+        .success()
+}
+
+#[test]
+fn finish_and_delete_respects_merge_state() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let merged = VibeSession::<Idle>::in_repo(repo, "merged-branch")
+        .start()
+        .unwrap();
+    let finished = merged.finish_and_delete(false).unwrap();
+    assert_eq!(finished.branch().as_ref(), "merged-branch");
+    assert!(!branch_exists(repo, "merged-branch"));
+
+    let unmerged = VibeSession::<Idle>::in_repo(repo, "unmerged-branch")
+        .start()
+        .unwrap();
+    git(repo, &["commit", "-q", "--allow-empty", "-m", "work"]);
+    let Err((unmerged, err)) = unmerged.finish_and_delete(false) else {
+        panic!("unmerged branch was deleted");
+    };
+    assert!(matches!(err, VibeError::UnmergedBranch(ref b) if b == "unmerged-branch"));
+    assert!(branch_exists(repo, "unmerged-branch"));
+    assert_eq!(unmerged.branch().as_str(), "unmerged-branch");
+    assert_eq!(git(repo, &["branch", "--show-current"]), "unmerged-branch");
+    unmerged.finish();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "main");
+
+    let forced = VibeSession::<Idle>::in_repo(repo, "forced-branch")
+        .start()
+        .unwrap();
+    git(
+        repo,
+        &["commit", "-q", "--allow-empty", "-m", "forced work"],
+    );
+    assert!(forced.finish_and_delete(true).is_ok());
+    assert!(!branch_exists(repo, "forced-branch"));
+}
+
+#[test]
+fn finish_and_delete_reports_a_failed_checkout() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::write(repo.join("file.txt"), "base\n").unwrap();
+    git(repo, &["add", "file.txt"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+
+    let session = VibeSession::<Idle>::in_repo(repo, "dirty-vibe")
+        .start()
+        .unwrap();
+    fs::write(repo.join("file.txt"), "vibe\n").unwrap();
+    git(repo, &["commit", "-qam", "vibe"]);
+    // Uncommitted changes that checking out the base would overwrite.
+    fs::write(repo.join("file.txt"), "dirty\n").unwrap();
+
+    let result = session.finish_and_delete(true);
+    assert!(matches!(result, Err((_, VibeError::Git { .. }))));
+    assert_eq!(git(repo, &["branch", "--show-current"]), "dirty-vibe");
+    assert_eq!(
+        fs::read_to_string(repo.join("file.txt")).unwrap(),
+        "dirty\n"
+    );
+}
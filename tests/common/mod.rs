@@ -0,0 +1,32 @@
+//! Helpers shared by the integration tests that drive a repository by path.
+#![allow(dead_code)]
+
+use std::{path::Path, process::Command};
+
+/// Build a git command that runs inside `repo`.
+pub fn git_command(repo: &Path) -> Command {
+    let mut command = Command::new("git");
+    command.current_dir(repo);
+    command
+}
+
+/// Run git in `repo`, asserting it succeeds, and return its trimmed stdout.
+pub fn git(repo: &Path, args: &[&str]) -> String {
+    let output = git_command(repo).args(args).output().unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8(output.stdout).unwrap().trim().to_string()
+}
+
+/// Create a repository on `main` with a test identity and no commits.
+pub fn init_empty_repo(repo: &Path) {
+    git(repo, &["init", "-q", "-b", "main"]);
+    git(repo, &["config", "user.email", "test@example.com"]);
+    git(repo, &["config", "user.name", "Test User"]);
+}
+
+/// Create a repository on `main` with a test identity and an empty root
+/// commit.
+pub fn init_repo(repo: &Path) {
+    init_empty_repo(repo);
+    git(repo, &["commit", "-q", "--allow-empty", "-m", "init"]);
+}
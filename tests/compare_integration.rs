@@ -0,0 +1,41 @@
+use std::{fs, path::Path};
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{git, init_repo};
+
+fn commit_file(repo: &Path, branch: &str, path: &str, contents: &str) {
+    git(repo, &["checkout", "-q", "-b", branch, "main"]);
+    fs::write(repo.join(path), contents).unwrap();
+    git(repo, &["add", path]);
+    git(repo, &["commit", "-qm", branch]);
+}
+
+#[test]
+fn compare_branches_reports_differences() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    commit_file(repo, "approach-a", "a.txt", "first approach\n");
+    commit_file(repo, "approach-b", "b.txt", "second approach\n");
+
+    let client = McpClient::new().with_repo(repo);
+    let stat = client
+        .compare_branches(&"approach-a".into(), &"approach-b".into(), false)
+        .unwrap();
+    assert!(stat.contains("a.txt"));
+    assert!(stat.contains("b.txt"));
+    assert!(stat.contains("2 files changed"));
+    assert!(!stat.contains("second approach"));
+
+    let patch = client
+        .compare_branches(&"approach-a".into(), &"approach-b".into(), true)
+        .unwrap();
+    assert!(patch.contains("-first approach"));
+    assert!(patch.contains("+second approach"));
+
+    let missing = client.compare_branches(&"approach-a".into(), &"missing".into(), false);
+    assert!(matches!(missing, Err(VibeError::UnknownBranch(ref b)) if b == "missing"));
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn diff_since_shows_only_changes_after_the_checkpoint() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("checkpoint-vibe").unwrap();
+    fs::write(repo.join("before.txt"), "before checkpoint\n\n").unwrap();
+    git(repo, &["add", "before.txt"]);
+    git(repo, &["commit", "-m", "checkpoint"]);
+    let checkpoint = git(repo, &["rev-parse", "HEAD"]);
+
+    fs::write(repo.join("added.txt"), "committed later\n").unwrap();
+    git(repo, &["add", "added.txt"]);
+    git(repo, &["commit", "-m", "later"]);
+    fs::write(repo.join("before.txt"), "edited, not committed\n\n").unwrap();
+
+    let diff = client.diff_since(&checkpoint[..10]).unwrap();
+    assert!(diff.contains("+committed later"));
+    assert!(diff.contains("+edited, not committed"));
+    assert!(diff.contains("-before checkpoint"));
+    assert!(!diff.contains("+before checkpoint"));
+    // The trailing blank context line of the last hunk is kept.
+    assert!(diff.ends_with("+edited, not committed\n \n"));
+
+    let base = git(repo, &["rev-parse", "main"]);
+    let outside = client.diff_since(&base);
+    assert!(matches!(outside, Err(VibeError::NotASessionCommit(ref c)) if *c == base));
+    let unknown = client.diff_since("no-such-commit");
+    assert!(matches!(unknown, Err(VibeError::NotASessionCommit(_))));
+}
@@ -0,0 +1,92 @@
+use std::path::Path;
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{git, init_repo};
+
+fn commit_empty(repo: &Path, message: &str) {
+    git(repo, &["commit", "-q", "--allow-empty", "-m", message]);
+}
+
+#[test]
+fn divergence_counts_commits_behind_an_advanced_base() {
+    let dir = tempdir().unwrap();
+    let upstream = dir.path().join("upstream");
+    let clone = dir.path().join("clone");
+    std::fs::create_dir(&upstream).unwrap();
+    init_repo(&upstream);
+    let upstream_url = upstream.to_str().unwrap();
+    git(dir.path(), &["clone", "-q", upstream_url, "clone"]);
+    git(&clone, &["config", "user.email", "test@example.com"]);
+    git(&clone, &["config", "user.name", "Test User"]);
+
+    let mut client = McpClient::new().with_repo(&clone);
+    client.start_vibing("diverged-branch").unwrap();
+    commit_empty(&clone, "session work");
+    commit_empty(&clone, "more session work");
+
+    git(&clone, &["checkout", "-q", "main"]);
+    commit_empty(&clone, "local base work");
+    git(&clone, &["checkout", "-q", "diverged-branch"]);
+
+    let local = client.divergence(None).unwrap();
+    assert_eq!((local.ahead, local.behind), (2, 1));
+    assert_eq!(local.to_string(), "2 commits ahead, 1 behind main");
+
+    commit_empty(&upstream, "upstream one");
+    commit_empty(&upstream, "upstream two");
+    commit_empty(&upstream, "upstream three");
+
+    let fetched = client.divergence(Some("origin")).unwrap();
+    assert_eq!(fetched.base, "origin/main");
+    assert_eq!((fetched.ahead, fetched.behind), (2, 3));
+}
+
+#[test]
+fn divergence_fetches_the_base_without_a_fetch_refspec() {
+    let dir = tempdir().unwrap();
+    let upstream = dir.path().join("upstream");
+    let clone = dir.path().join("clone");
+    std::fs::create_dir(&upstream).unwrap();
+    init_repo(&upstream);
+    let upstream_url = upstream.to_str().unwrap();
+    git(dir.path(), &["clone", "-q", upstream_url, "clone"]);
+    git(&clone, &["config", "user.email", "test@example.com"]);
+    git(&clone, &["config", "user.name", "Test User"]);
+    // Without a fetch refspec a plain fetch leaves origin/main stale.
+    git(&clone, &["config", "--unset-all", "remote.origin.fetch"]);
+
+    let mut client = McpClient::new().with_repo(&clone);
+    client.start_vibing("narrow-vibe").unwrap();
+    for n in 1..=2 {
+        commit_empty(&upstream, &format!("upstream {n}"));
+    }
+
+    let fetched = client.divergence(Some("origin")).unwrap();
+    assert_eq!(fetched.base, "origin/main");
+    assert_eq!((fetched.ahead, fetched.behind), (0, 2));
+    assert_eq!(
+        git(&clone, &["rev-parse", "origin/main"]),
+        git(&upstream, &["rev-parse", "main"])
+    );
+}
+
+#[test]
+fn divergence_rejects_an_option_like_remote() {
+    let dir = tempdir().unwrap();
+    init_repo(dir.path());
+    let marker = dir.path().join("pwned");
+
+    let mut client = McpClient::new().with_repo(dir.path());
+    client.start_vibing("remote-vibe").unwrap();
+    let remote = format!("--upload-pack=touch {}", marker.display());
+
+    let err = client.divergence(Some(&remote)).unwrap_err();
+    assert!(matches!(err, VibeError::UnknownRemote(name) if name == remote));
+    assert!(matches!(
+        client.divergence(Some("origin")),
+        Err(VibeError::UnknownRemote(_))
+    ));
+    assert!(!marker.exists());
+}
@@ -0,0 +1,25 @@
+use std::{thread, time::Duration};
+use tempfile::tempdir;
+use vibe_git::{format_elapsed, McpClient};
+
+mod common;
+use common::init_repo;
+
+#[test]
+fn status_reports_elapsed_time() {
+    let dir = tempdir().unwrap();
+    init_repo(dir.path());
+
+    let mut client = McpClient::new().with_repo(dir.path());
+    assert!(client.elapsed().is_none());
+    client.start_vibing("elapsed-branch").unwrap();
+    thread::sleep(Duration::from_millis(50));
+    assert!(client.elapsed().unwrap() >= Duration::from_millis(50));
+
+    assert_eq!(format_elapsed(Duration::from_secs(750)), "12m 30s");
+    assert_eq!(format_elapsed(Duration::from_secs(3723)), "1h 2m 3s");
+    assert_eq!(format_elapsed(Duration::from_secs(9)), "9s");
+
+    client.stop_vibing().unwrap();
+    assert!(client.elapsed().is_none());
+}
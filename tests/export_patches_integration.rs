@@ -0,0 +1,83 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, git_command, init_empty_repo};
+
+#[test]
+fn exported_patches_apply_onto_base() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::write(repo.join("file.txt"), "base\n").unwrap();
+    // Ends in a blank line, so its hunk ends in whitespace-only context.
+    fs::write(repo.join("notes.txt"), "notes\n\n").unwrap();
+    git(repo, &["add", "file.txt", "notes.txt"]);
+    git(repo, &["commit", "-qm", "init"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("patch-branch").unwrap();
+    fs::write(repo.join("notes.txt"), "edited notes\n\n").unwrap();
+    for contents in ["base\nsecond\n", "base\nsecond\nthird\n"] {
+        fs::write(repo.join("file.txt"), contents).unwrap();
+        git(repo, &["commit", "-qam", "extend file"]);
+    }
+
+    let out = tempdir().unwrap();
+    let series = client
+        .export_patches(&out.path().join("series"), false)
+        .unwrap();
+    assert_eq!(series.len(), 2);
+    let squashed = client
+        .export_patches(&out.path().join("squashed"), true)
+        .unwrap();
+    assert_eq!(squashed.len(), 1);
+
+    git(repo, &["checkout", "-q", "-b", "apply-series", "main"]);
+    let am = git_command(repo)
+        .arg("am")
+        .arg("-q")
+        .args(&series)
+        .status()
+        .unwrap();
+    assert!(am.success());
+    assert_eq!(
+        fs::read_to_string(repo.join("file.txt")).unwrap(),
+        "base\nsecond\nthird\n"
+    );
+
+    git(repo, &["checkout", "-q", "-b", "apply-squashed", "main"]);
+    let apply = git_command(repo)
+        .arg("apply")
+        .arg(&squashed[0])
+        .status()
+        .unwrap();
+    assert!(apply.success());
+    assert_eq!(
+        fs::read_to_string(repo.join("file.txt")).unwrap(),
+        "base\nsecond\nthird\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo.join("notes.txt")).unwrap(),
+        "edited notes\n\n"
+    );
+
+    // A binary file sorted last: its block must keep the closing blank line.
+    let binary = [0u8, 159, 146, 150, 255, 0, 10, 10];
+    git(repo, &["checkout", "-q", "-f", "patch-branch"]);
+    fs::write(repo.join("zz.bin"), binary).unwrap();
+    git(repo, &["add", "zz.bin"]);
+    git(repo, &["commit", "-qm", "add binary"]);
+    let with_binary = client
+        .export_patches(&out.path().join("binary"), true)
+        .unwrap();
+    git(repo, &["checkout", "-q", "-b", "apply-binary", "main"]);
+    let apply = git_command(repo)
+        .arg("apply")
+        .arg(&with_binary[0])
+        .status()
+        .unwrap();
+    assert!(apply.success());
+    assert_eq!(fs::read(repo.join("zz.bin")).unwrap(), binary);
+}
@@ -0,0 +1,38 @@
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::PermissionsExt};
+use tempfile::tempdir;
+use vibe_git::{McpClient, GIT_BINARY_ENV};
+
+mod common;
+use common::init_repo;
+
+#[test]
+fn configured_git_binary_is_invoked() {
+    let dir = tempdir().unwrap();
+    init_repo(dir.path());
+
+    let tools = tempdir().unwrap();
+    let log = tools.path().join("invocations.log");
+    let wrapper = tools.path().join("git-wrapper");
+    fs::write(
+        &wrapper,
+        format!(
+            "#!/bin/sh\necho \"$*\" >> '{}'\nexec git \"$@\"\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::set_var(GIT_BINARY_ENV, &wrapper);
+
+    let mut client = McpClient::new().with_repo(dir.path());
+    client.start_vibing("wrapped-branch").unwrap();
+    client.stop_vibing().unwrap();
+
+    let invocations = fs::read_to_string(&log).unwrap();
+    assert!(invocations
+        .lines()
+        .any(|l| l == "checkout -b wrapped-branch main"));
+    assert!(invocations.lines().any(|l| l == "checkout main"));
+}
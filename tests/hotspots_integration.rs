@@ -0,0 +1,58 @@
+use std::{fs, path::Path};
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_repo};
+
+fn commit_all(repo: &Path, message: &str) {
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-qm", message]);
+}
+
+#[test]
+fn most_edited_file_ranks_first() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("hotspot-branch").unwrap();
+    assert!(client.hotspots().unwrap().is_empty());
+
+    fs::write(repo.join("other.txt"), "one\ntwo\nthree\nfour\n").unwrap();
+    commit_all(repo, "add other");
+    let mut contents = String::new();
+    for line in ["a", "b", "c"] {
+        contents.push_str(line);
+        contents.push('\n');
+        fs::write(repo.join("busy.txt"), &contents).unwrap();
+        commit_all(repo, "edit busy");
+    }
+
+    let hotspots = client.hotspots().unwrap();
+    assert_eq!(hotspots.len(), 2);
+    assert_eq!(hotspots[0].path, "busy.txt");
+    assert_eq!(hotspots[0].commits, 3);
+    assert_eq!(hotspots[0].net_lines(), 3);
+    assert_eq!(hotspots[1].path, "other.txt");
+    assert_eq!(hotspots[1].commits, 1);
+    assert_eq!(hotspots[1].insertions, 4);
+}
+
+#[test]
+fn hotspots_report_non_ascii_paths_verbatim() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("unicode-hotspots").unwrap();
+    fs::write(repo.join("résumé.txt"), "one\ntwo\n").unwrap();
+    commit_all(repo, "add résumé");
+
+    let hotspots = client.hotspots().unwrap();
+    assert_eq!(hotspots.len(), 1);
+    assert_eq!(hotspots[0].path, "résumé.txt");
+    assert_eq!(hotspots[0].insertions, 2);
+}
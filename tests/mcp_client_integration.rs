@@ -25,7 +25,7 @@ fn mcp_client_switches_branches() {
         .unwrap();
 
     let mut client = McpClient::new();
-    client.start_vibing("integration-branch");
+    client.start_vibing("integration-branch").unwrap();
 
     let branch = String::from_utf8(
         Command::new("git")
@@ -37,7 +37,7 @@ fn mcp_client_switches_branches() {
     .unwrap();
     assert_eq!(branch.trim(), "integration-branch");
 
-    client.stop_vibing();
+    client.stop_vibing().unwrap();
     let branch = String::from_utf8(
         Command::new("git")
             .args(["rev-parse", "--abbrev-ref", "HEAD"])
@@ -0,0 +1,34 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn stop_appends_session_summary() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let metrics = repo.join("metrics/metrics.jsonl");
+    let mut client = McpClient::new().with_repo(repo).with_metrics(&metrics);
+    client.start_vibing("metrics-branch").unwrap();
+    for contents in ["one\n", "one\ntwo\nthree\n"] {
+        fs::write(repo.join("notes.txt"), contents).unwrap();
+        git(repo, &["add", "notes.txt"]);
+        git(repo, &["commit", "-qm", "update notes"]);
+    }
+    client.stop_vibing().unwrap();
+
+    let contents = fs::read_to_string(&metrics).unwrap();
+    let lines: Vec<_> = contents.lines().collect();
+    assert_eq!(lines.len(), 1);
+    let line = lines[0];
+    assert!(line.starts_with("{\"branch\":\"metrics-branch\","));
+    assert!(line.contains("\"started_at\":"));
+    assert!(line.contains("\"stopped_at\":"));
+    assert!(line.contains("\"commit_count\":2"));
+    assert!(line.contains("\"insertions\":3"));
+    assert!(line.contains("\"deletions\":0"));
+}
@@ -0,0 +1,37 @@
+use tempfile::tempdir;
+use vibe_git::{Idle, McpClient, VibeSession};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn sessions_drive_separate_repositories() {
+    let first = tempdir().unwrap();
+    let second = tempdir().unwrap();
+    init_repo(first.path());
+    init_repo(second.path());
+
+    let one = VibeSession::<Idle>::in_repo(first.path(), "vibe-one")
+        .start()
+        .unwrap();
+    let mut client = McpClient::new().with_repo(second.path());
+    client.start_vibing("vibe-two").unwrap();
+
+    assert_eq!(git(first.path(), &["branch", "--show-current"]), "vibe-one");
+    assert_eq!(
+        git(second.path(), &["branch", "--show-current"]),
+        "vibe-two"
+    );
+    assert_eq!(git(first.path(), &["branch", "--list", "vibe-two"]), "");
+
+    let finished = one.finish();
+    assert_eq!(finished.branch().as_str(), "vibe-one");
+    assert_eq!(git(first.path(), &["branch", "--show-current"]), "main");
+    assert_eq!(
+        git(second.path(), &["branch", "--show-current"]),
+        "vibe-two"
+    );
+
+    client.stop_vibing().unwrap();
+    assert_eq!(git(second.path(), &["branch", "--show-current"]), "main");
+}
@@ -0,0 +1,53 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_empty_repo};
+
+#[test]
+fn orphan_session_starts_without_history() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::write(repo.join("src.txt"), "source\n").unwrap();
+    git(repo, &["add", "src.txt"]);
+    git(repo, &["commit", "-m", "init"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_orphan("snapshots").unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "snapshots");
+
+    git(repo, &["rm", "-qf", "src.txt"]);
+    fs::write(repo.join("index.html"), "snapshot\n").unwrap();
+    git(repo, &["add", "index.html"]);
+    git(repo, &["commit", "-m", "snapshot"]);
+
+    assert_eq!(git(repo, &["rev-list", "--count", "HEAD"]), "1");
+    assert_eq!(git(repo, &["log", "-1", "--format=%P"]), "");
+    assert_eq!(git(repo, &["ls-tree", "--name-only", "HEAD"]), "index.html");
+
+    client.stop_vibing().unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "main");
+}
+
+#[test]
+fn orphan_session_can_take_its_files_from_a_ref() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::write(repo.join("src.txt"), "v1\n").unwrap();
+    git(repo, &["add", "src.txt"]);
+    git(repo, &["commit", "-m", "v1"]);
+    git(repo, &["tag", "v1"]);
+    fs::write(repo.join("src.txt"), "v2\n").unwrap();
+    git(repo, &["commit", "-qam", "v2"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_orphan_from("release", "v1").unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "release");
+    assert_eq!(fs::read_to_string(repo.join("src.txt")).unwrap(), "v1\n");
+
+    git(repo, &["commit", "-m", "release"]);
+    assert_eq!(git(repo, &["rev-list", "--count", "HEAD"]), "1");
+}
@@ -0,0 +1,27 @@
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn vibing_on_a_protected_branch_is_refused() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    let result = client.start_vibing("main");
+    assert!(matches!(result, Err(VibeError::ProtectedBranch(ref b)) if b == "main"));
+    assert!(client.branch().is_none());
+
+    let mut client = McpClient::new()
+        .with_repo(repo)
+        .with_protected_branches(["release"]);
+    let result = client.start_vibing("release");
+    assert!(matches!(result, Err(VibeError::ProtectedBranch(ref b)) if b == "release"));
+    assert_eq!(git(repo, &["branch", "--list", "release"]), "");
+
+    client.start_vibing("master").unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "master");
+}
@@ -0,0 +1,100 @@
+use std::{path::Path, time::Duration};
+use tempfile::tempdir;
+use vibe_git::{McpClient, PruneOptions};
+
+mod common;
+use common::{git, git_command, init_empty_repo};
+
+/// Make an empty commit dated long before any pruning cutoff.
+fn commit_old(repo: &Path, message: &str) {
+    let status = git_command(repo)
+        .args(["commit", "-q", "--allow-empty", "-m", message])
+        .env("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn only_old_merged_vibe_branches_are_pruned() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    commit_old(repo, "init");
+
+    // Old and merged: points at the old root commit.
+    git(repo, &["branch", "vibe-old-merged"]);
+    // Old but unmerged.
+    git(repo, &["checkout", "-q", "-b", "vibe-old-unmerged"]);
+    commit_old(repo, "abandoned");
+    // Recent.
+    git(repo, &["checkout", "-q", "-b", "vibe-recent", "main"]);
+    git(repo, &["commit", "-q", "--allow-empty", "-m", "recent"]);
+    git(repo, &["checkout", "-q", "main"]);
+    git(repo, &["branch", "other-old"]);
+
+    let client = McpClient::new().with_repo(repo);
+    let mut options = PruneOptions {
+        pattern: "vibe-*".to_string(),
+        min_age: Duration::from_secs(30 * 24 * 60 * 60),
+        confirm: false,
+        force: false,
+    };
+    let listed = client.prune_vibe_branches(&options).unwrap();
+    let names: Vec<_> = listed
+        .iter()
+        .map(|b| (b.name.as_str(), b.merged, b.deleted))
+        .collect();
+    assert_eq!(
+        names,
+        [
+            ("vibe-old-merged", true, false),
+            ("vibe-old-unmerged", false, false),
+        ]
+    );
+
+    options.confirm = true;
+    client.prune_vibe_branches(&options).unwrap();
+    let remaining = git(repo, &["branch", "--format=%(refname:short)"]);
+    let remaining: Vec<_> = remaining.lines().collect();
+    assert_eq!(
+        remaining,
+        ["main", "other-old", "vibe-old-unmerged", "vibe-recent"]
+    );
+}
+
+#[test]
+fn branches_checked_out_in_a_worktree_are_skipped() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let linked = dir.path().join("linked");
+    std::fs::create_dir(&repo).unwrap();
+    init_empty_repo(&repo);
+    commit_old(&repo, "init");
+    for name in ["vibe-a", "vibe-b", "vibe-c"] {
+        git(&repo, &["branch", name]);
+    }
+    git(
+        &repo,
+        &["worktree", "add", "-q", linked.to_str().unwrap(), "vibe-b"],
+    );
+
+    let client = McpClient::new().with_repo(&repo);
+    let pruned = client
+        .prune_vibe_branches(&PruneOptions {
+            pattern: "vibe-*".to_string(),
+            min_age: Duration::from_secs(30 * 24 * 60 * 60),
+            confirm: true,
+            force: false,
+        })
+        .unwrap();
+    let names: Vec<_> = pruned
+        .iter()
+        .map(|b| (b.name.as_str(), b.deleted))
+        .collect();
+    assert_eq!(names, [("vibe-a", true), ("vibe-c", true)]);
+    assert_eq!(
+        git(&repo, &["branch", "--format=%(refname:short)"]),
+        "main\nvibe-b"
+    );
+}
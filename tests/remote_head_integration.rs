@@ -0,0 +1,47 @@
+use tempfile::tempdir;
+use vibe_git::{Idle, VibeSession};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn remote_default_branch_is_preferred_as_base() {
+    let dir = tempdir().unwrap();
+    let upstream = dir.path().join("upstream");
+    let clone = dir.path().join("clone");
+    std::fs::create_dir(&upstream).unwrap();
+    init_repo(&upstream);
+    git(&upstream, &["branch", "-m", "trunk"]);
+    git(
+        dir.path(),
+        &["clone", "-q", upstream.to_str().unwrap(), "clone"],
+    );
+    // A local `main` would win over `trunk` without remote detection.
+    git(&clone, &["branch", "main"]);
+
+    let vibing = VibeSession::<Idle>::in_repo(&clone, "cached-head")
+        .start()
+        .unwrap();
+    assert_eq!(vibing.base().as_ref(), "trunk");
+    vibing.finish();
+
+    git(&clone, &["remote", "set-head", "origin", "--delete"]);
+    let vibing = VibeSession::<Idle>::in_repo(&clone, "no-query")
+        .start()
+        .unwrap();
+    assert_eq!(vibing.base().as_ref(), "main");
+    vibing.finish();
+
+    let vibing = VibeSession::<Idle>::in_repo(&clone, "queried-head")
+        .with_remote_head_query(true)
+        .start()
+        .unwrap();
+    assert_eq!(vibing.base().as_ref(), "trunk");
+    assert_eq!(
+        git(
+            &clone,
+            &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"]
+        ),
+        "origin/trunk"
+    );
+}
@@ -0,0 +1,31 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn repo_impact_counts_objects_added_by_the_session() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("impact-vibe").unwrap();
+    let before = client.repo_impact().unwrap();
+    assert_eq!(before.objects_added, 0);
+
+    for round in 0..3 {
+        fs::write(repo.join("notes.txt"), format!("round {round}\n")).unwrap();
+        git(repo, &["add", "notes.txt"]);
+        git(repo, &["commit", "-m", &format!("round {round}")]);
+    }
+
+    let impact = client.repo_impact().unwrap();
+    // Each round adds a blob, a tree and a commit.
+    assert_eq!(impact.objects_added, 9);
+    assert!(impact.kib_added >= 0);
+    assert!(!impact.suggests_gc());
+    assert!(impact.to_string().starts_with("9 objects"));
+}
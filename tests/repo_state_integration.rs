@@ -0,0 +1,39 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{git, git_command, init_empty_repo};
+
+#[test]
+fn start_is_refused_during_merge() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::write(repo.join("file.txt"), "base\n").unwrap();
+    git(repo, &["add", "file.txt"]);
+    git(repo, &["commit", "-qm", "init"]);
+    git(repo, &["checkout", "-qb", "other"]);
+    fs::write(repo.join("file.txt"), "other\n").unwrap();
+    git(repo, &["commit", "-qam", "other"]);
+    git(repo, &["checkout", "-q", "main"]);
+    fs::write(repo.join("file.txt"), "main\n").unwrap();
+    git(repo, &["commit", "-qam", "main"]);
+    let merge = git_command(repo)
+        .args(["merge", "-q", "other"])
+        .output()
+        .unwrap();
+    assert!(!merge.status.success(), "merge should conflict");
+
+    let mut client = McpClient::new().with_repo(repo);
+    let result = client.start_vibing("merge-branch");
+    assert!(matches!(
+        result,
+        Err(VibeError::OperationInProgress("merge"))
+    ));
+    assert!(client.branch().is_none());
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("merge in progress"));
+}
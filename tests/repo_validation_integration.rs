@@ -0,0 +1,44 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{init_empty_repo, init_repo};
+
+#[test]
+fn start_distinguishes_missing_and_corrupt_repositories() {
+    let missing = tempdir().unwrap();
+    let result = McpClient::new()
+        .with_repo(missing.path())
+        .start_vibing("vibe");
+    assert!(matches!(result, Err(VibeError::NotARepository)));
+
+    let broken_head = tempdir().unwrap();
+    init_repo(broken_head.path());
+    fs::write(broken_head.path().join(".git/HEAD"), "garbage\n").unwrap();
+    let result = McpClient::new()
+        .with_repo(broken_head.path())
+        .start_vibing("vibe");
+    assert!(matches!(result, Err(VibeError::CorruptRepository(_))));
+
+    let missing_object = tempdir().unwrap();
+    init_empty_repo(missing_object.path());
+    fs::write(
+        missing_object.path().join(".git/refs/heads/main"),
+        "0123456789abcdef0123456789abcdef01234567\n",
+    )
+    .unwrap();
+    let result = McpClient::new()
+        .with_repo(missing_object.path())
+        .start_vibing("vibe");
+    assert!(matches!(result, Err(VibeError::CorruptRepository(_))));
+}
+
+#[test]
+fn start_reports_a_missing_directory_as_an_io_error() {
+    let dir = tempdir().unwrap();
+    let result = McpClient::new()
+        .with_repo(dir.path().join("missing"))
+        .start_vibing("vibe");
+    assert!(matches!(result, Err(VibeError::Io(_))));
+}
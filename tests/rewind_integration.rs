@@ -0,0 +1,82 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::{McpClient, ResetMode, VibeError};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn rewind_moves_head_back_within_the_session() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+    let head_subject = || git(repo, &["log", "-1", "--format=%s"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("rewind-branch").unwrap();
+    for n in 1..=3 {
+        fs::write(repo.join("file.txt"), format!("version {n}\n")).unwrap();
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "-qm", &format!("commit {n}")]);
+    }
+
+    let result = client.rewind(4, ResetMode::Hard);
+    assert!(matches!(
+        result,
+        Err(VibeError::RewindPastBase {
+            requested: 4,
+            available: 3
+        })
+    ));
+
+    client.rewind(2, ResetMode::Hard).unwrap();
+    assert_eq!(head_subject(), "commit 1");
+    assert_eq!(
+        fs::read_to_string(repo.join("file.txt")).unwrap(),
+        "version 1\n"
+    );
+
+    fs::write(repo.join("file.txt"), "version 2\n").unwrap();
+    git(repo, &["commit", "-qam", "commit 2 again"]);
+    client.rewind(1, ResetMode::Soft).unwrap();
+    assert_eq!(head_subject(), "commit 1");
+    assert_eq!(
+        fs::read_to_string(repo.join("file.txt")).unwrap(),
+        "version 2\n"
+    );
+}
+
+#[test]
+fn rewind_counts_a_merge_as_one_step() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+    git(repo, &["commit", "-q", "--allow-empty", "-m", "base tip"]);
+    git(repo, &["checkout", "-q", "-b", "side", "main~1"]);
+    for n in 1..=3 {
+        git(
+            repo,
+            &["commit", "-q", "--allow-empty", "-m", &format!("side {n}")],
+        );
+    }
+    git(repo, &["checkout", "-q", "main"]);
+    let base_tip = git(repo, &["rev-parse", "main"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("merge-vibe").unwrap();
+    git(
+        repo,
+        &["merge", "-q", "--no-ff", "-m", "merge side", "side"],
+    );
+
+    let result = client.rewind(2, ResetMode::Soft);
+    assert!(matches!(
+        result,
+        Err(VibeError::RewindPastBase {
+            requested: 2,
+            available: 1
+        })
+    ));
+    client.rewind(1, ResetMode::Hard).unwrap();
+    assert_eq!(git(repo, &["rev-parse", "HEAD"]), base_tip);
+}
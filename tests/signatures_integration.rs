@@ -0,0 +1,49 @@
+use std::{fs, process::Command};
+use tempfile::tempdir;
+use vibe_git::{McpClient, SignatureStatus};
+
+mod common;
+use common::{git, init_empty_repo};
+
+#[test]
+fn signed_and_unsigned_commits_are_reported() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let key = dir.path().join("signing-key");
+    fs::create_dir(&repo).unwrap();
+    let keygen = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-f"])
+        .arg(&key)
+        .status()
+        .expect("ssh-keygen is needed to sign the test commits");
+    assert!(keygen.success(), "ssh-keygen failed to create a key");
+    let public_key = fs::read_to_string(key.with_extension("pub")).unwrap();
+    let allowed = dir.path().join("allowed-signers");
+    fs::write(&allowed, format!("test@example.com {public_key}")).unwrap();
+
+    init_empty_repo(&repo);
+    git(&repo, &["config", "gpg.format", "ssh"]);
+    git(&repo, &["config", "user.signingkey", key.to_str().unwrap()]);
+    let allowed = allowed.to_str().unwrap();
+    git(&repo, &["config", "gpg.ssh.allowedSignersFile", allowed]);
+    git(&repo, &["commit", "--allow-empty", "-m", "init"]);
+
+    let mut client = McpClient::new().with_repo(&repo);
+    client.start_vibing("signed-vibe").unwrap();
+    git(&repo, &["commit", "--allow-empty", "-S", "-m", "signed"]);
+    git(&repo, &["commit", "--allow-empty", "-m", "unsigned"]);
+
+    let commits = client.verify_signatures().unwrap();
+    let statuses: Vec<_> = commits
+        .iter()
+        .map(|commit| (commit.summary.as_str(), commit.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            ("signed", SignatureStatus::Verified),
+            ("unsigned", SignatureStatus::Unsigned),
+        ]
+    );
+    assert_eq!(commits[0].commit, git(&repo, &["rev-parse", "HEAD~1"]));
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_empty_repo};
+
+#[test]
+fn squash_patch_reproduces_the_session_on_a_fresh_base() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let fresh = dir.path().join("fresh");
+    fs::create_dir(&repo).unwrap();
+    init_empty_repo(&repo);
+    // Ends in a blank line, so its hunk ends in whitespace-only context.
+    fs::write(repo.join("keep.txt"), "original\n\n").unwrap();
+    fs::write(repo.join("drop.txt"), "to be removed\n").unwrap();
+    git(&repo, &["add", "."]);
+    git(&repo, &["commit", "-m", "init"]);
+
+    let mut client = McpClient::new().with_repo(&repo);
+    client.start_vibing("patch-vibe").unwrap();
+    assert_eq!(client.squash_patch().unwrap(), "");
+
+    fs::write(repo.join("added.txt"), "committed\n").unwrap();
+    git(&repo, &["add", "added.txt"]);
+    git(&repo, &["rm", "-q", "drop.txt"]);
+    git(&repo, &["commit", "-m", "session work"]);
+    fs::write(repo.join("keep.txt"), "edited, not committed\n\n").unwrap();
+    let head = git(&repo, &["rev-parse", "HEAD"]);
+
+    let patch = client.squash_patch().unwrap();
+    assert_eq!(git(&repo, &["rev-parse", "HEAD"]), head);
+    assert_eq!(git(&repo, &["rev-list", "--count", "HEAD"]), "2");
+
+    let repo_url = repo.to_str().unwrap();
+    git(
+        dir.path(),
+        &["clone", "-q", "-b", "main", repo_url, "fresh"],
+    );
+    fs::write(dir.path().join("session.patch"), &patch).unwrap();
+    git(&fresh, &["apply", "--index", "../session.patch"]);
+
+    let read = |name: &str| fs::read_to_string(fresh.join(name)).unwrap();
+    assert_eq!(read("keep.txt"), "edited, not committed\n\n");
+    assert_eq!(read("added.txt"), "committed\n");
+    assert!(!fresh.join("drop.txt").exists());
+
+    // A staged binary file sorted last: its block must keep the closing
+    // blank line.
+    let binary = [0u8, 159, 146, 150, 255, 0, 10, 10];
+    fs::write(repo.join("zz.bin"), binary).unwrap();
+    git(&repo, &["add", "zz.bin"]);
+    let patch = client.squash_patch().unwrap();
+    git(&fresh, &["reset", "-q", "--hard"]);
+    fs::write(dir.path().join("session.patch"), &patch).unwrap();
+    git(&fresh, &["apply", "--index", "../session.patch"]);
+    assert_eq!(fs::read(fresh.join("zz.bin")).unwrap(), binary);
+    assert_eq!(read("keep.txt"), "edited, not committed\n\n");
+}
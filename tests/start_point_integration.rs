@@ -0,0 +1,45 @@
+use tempfile::tempdir;
+use vibe_git::{McpClient, VibeError};
+
+mod common;
+use common::{git, init_empty_repo, init_repo};
+
+#[test]
+fn session_can_start_from_a_tag() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    git(repo, &["commit", "--allow-empty", "-m", "v1"]);
+    git(repo, &["tag", "-a", "v1.0", "-m", "release"]);
+    let release = git(repo, &["rev-parse", "HEAD"]);
+    git(repo, &["commit", "--allow-empty", "-m", "after release"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    let missing = client.start_vibing_from("hotfix", "v9.9");
+    assert!(matches!(missing, Err(VibeError::UnknownRevision(ref r)) if r == "v9.9"));
+    assert!(client.branch().is_none());
+
+    client.start_vibing_from("hotfix", "v1.0").unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "hotfix");
+    assert_eq!(git(repo, &["rev-parse", "HEAD"]), release);
+
+    git(repo, &["commit", "--allow-empty", "-m", "fix"]);
+    assert_eq!(git(repo, &["rev-parse", "HEAD~1"]), release);
+    client.stop_vibing().unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "main");
+}
+
+#[test]
+fn session_starts_from_the_base_while_on_another_branch() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+    let base = git(repo, &["rev-parse", "HEAD"]);
+    git(repo, &["checkout", "-q", "-b", "feature"]);
+    git(repo, &["commit", "--allow-empty", "-m", "feature work"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("from-base").unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "from-base");
+    assert_eq!(git(repo, &["rev-parse", "HEAD"]), base);
+}
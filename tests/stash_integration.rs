@@ -0,0 +1,40 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_empty_repo};
+
+#[test]
+fn stash_pop_restores_changes_exactly() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::write(repo.join("tracked.txt"), "original\n").unwrap();
+    git(repo, &["add", "tracked.txt"]);
+    git(repo, &["commit", "-q", "-m", "init"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("stash-branch").unwrap();
+    fs::write(repo.join("tracked.txt"), "edited\n").unwrap();
+    fs::write(repo.join("untracked.txt"), "scratch\n").unwrap();
+
+    assert!(client.stash().unwrap());
+    assert_eq!(
+        fs::read_to_string(repo.join("tracked.txt")).unwrap(),
+        "original\n"
+    );
+    assert!(!repo.join("untracked.txt").exists());
+    assert!(!client.stash().unwrap());
+
+    client.stash_pop().unwrap();
+    assert_eq!(
+        fs::read_to_string(repo.join("tracked.txt")).unwrap(),
+        "edited\n"
+    );
+    assert_eq!(
+        fs::read_to_string(repo.join("untracked.txt")).unwrap(),
+        "scratch\n"
+    );
+    assert!(client.stash_pop().is_err());
+}
@@ -0,0 +1,49 @@
+#![cfg(unix)]
+
+use std::{fs, os::unix::fs::PermissionsExt};
+use tempfile::tempdir;
+use vibe_git::{McpClient, StopOptions, GIT_BINARY_ENV};
+
+mod common;
+use common::init_repo;
+
+#[test]
+fn stop_runs_gc_only_when_asked() {
+    let dir = tempdir().unwrap();
+    init_repo(dir.path());
+
+    let tools = tempdir().unwrap();
+    let log = tools.path().join("invocations.log");
+    let wrapper = tools.path().join("git-wrapper");
+    fs::write(
+        &wrapper,
+        format!(
+            "#!/bin/sh\necho \"$*\" >> '{}'\nexec git \"$@\"\n",
+            log.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o755)).unwrap();
+    std::env::set_var(GIT_BINARY_ENV, &wrapper);
+    let gc_runs = || {
+        let invocations = fs::read_to_string(&log).unwrap();
+        invocations
+            .lines()
+            .filter(|l| *l == "gc --auto --quiet")
+            .count()
+    };
+
+    let mut client = McpClient::new().with_repo(dir.path());
+    client.start_vibing("plain-stop").unwrap();
+    client.stop_vibing().unwrap();
+    assert_eq!(gc_runs(), 0);
+
+    client.start_vibing("gc-stop").unwrap();
+    client
+        .stop_vibing_with(&StopOptions {
+            gc: true,
+            ..StopOptions::default()
+        })
+        .unwrap();
+    assert_eq!(gc_runs(), 1);
+}
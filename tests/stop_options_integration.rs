@@ -0,0 +1,75 @@
+use tempfile::tempdir;
+use vibe_git::{McpClient, StopOptions, VibeError};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn stop_can_stay_on_the_vibe_branch() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("stay-branch").unwrap();
+    client
+        .stop_vibing_with(&StopOptions {
+            return_to_base: false,
+            ..StopOptions::default()
+        })
+        .unwrap();
+    assert!(client.branch().is_none());
+    assert_eq!(git(repo, &["branch", "--show-current"]), "stay-branch");
+}
+
+#[test]
+fn stop_applies_stored_defaults() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    assert!(client.stop_defaults().return_to_base);
+    client.set_stop_defaults(StopOptions {
+        return_to_base: false,
+        ..StopOptions::default()
+    });
+
+    client.start_vibing("first-stay").unwrap();
+    client.stop_vibing().unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "first-stay");
+
+    client.start_vibing("second-stay").unwrap();
+    client.stop_vibing().unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "second-stay");
+
+    client.start_vibing("overridden").unwrap();
+    client.stop_vibing_with(&StopOptions::default()).unwrap();
+    assert_eq!(git(repo, &["branch", "--show-current"]), "main");
+}
+
+#[test]
+fn stop_keeps_the_session_when_the_base_cannot_be_checked_out() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+    std::fs::write(repo.join("notes.txt"), "base\n").unwrap();
+    git(repo, &["add", "notes.txt"]);
+    git(repo, &["commit", "-q", "-m", "notes"]);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("blocked-vibe").unwrap();
+    std::fs::write(repo.join("notes.txt"), "vibe\n").unwrap();
+    git(repo, &["commit", "-q", "-am", "vibe notes"]);
+    // Uncommitted changes that checking out the base would overwrite.
+    std::fs::write(repo.join("notes.txt"), "uncommitted\n").unwrap();
+
+    let result = client.stop_vibing();
+    assert!(matches!(result, Err(VibeError::Git { .. })));
+    assert_eq!(client.branch().unwrap().as_str(), "blocked-vibe");
+    assert_eq!(git(repo, &["branch", "--show-current"]), "blocked-vibe");
+
+    git(repo, &["checkout", "--", "notes.txt"]);
+    assert!(client.stop_vibing().unwrap().is_some());
+    assert_eq!(git(repo, &["branch", "--show-current"]), "main");
+}
@@ -0,0 +1,65 @@
+use std::{fs, path::Path};
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_empty_repo, init_repo};
+
+fn commit_all(repo: &Path, message: &str) {
+    git(repo, &["add", "-A"]);
+    git(repo, &["commit", "-qm", message]);
+}
+
+#[test]
+fn suggestion_summarizes_session_changes() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+    fs::create_dir_all(repo.join("src/auth")).unwrap();
+    fs::write(repo.join("src/auth/login.rs"), "fn login() {}\n").unwrap();
+    commit_all(repo, "init");
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("suggest-branch").unwrap();
+    assert_eq!(client.suggest_commit_message().unwrap(), None);
+
+    fs::write(
+        repo.join("src/auth/login.rs"),
+        "fn login() {\n    check();\n}\n",
+    )
+    .unwrap();
+    commit_all(repo, "wip");
+    fs::write(repo.join("src/auth/logout.rs"), "fn logout() {}\n").unwrap();
+    fs::write(
+        repo.join("src/auth/login.rs"),
+        "fn login() {\n    check();\n    audit();\n}\n",
+    )
+    .unwrap();
+    commit_all(repo, "wip");
+
+    let suggestion = client.suggest_commit_message().unwrap().unwrap();
+    let mut lines = suggestion.lines();
+    assert_eq!(lines.next(), Some("Update src/auth: 2 files changed"));
+    assert_eq!(lines.next(), Some(""));
+    assert_eq!(lines.next(), Some("- src/auth/login.rs (+4/-1)"));
+    assert_eq!(lines.next(), Some("- src/auth/logout.rs (+1/-0)"));
+    assert_eq!(lines.next(), None);
+}
+
+#[test]
+fn suggestion_keeps_non_ascii_paths_readable() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("unicode-suggest").unwrap();
+    fs::write(repo.join("naïve.txt"), "one\n").unwrap();
+    commit_all(repo, "add naïve");
+
+    let suggestion = client.suggest_commit_message().unwrap().unwrap();
+    let mut lines = suggestion.lines();
+    assert_eq!(lines.next(), Some("Add naïve.txt: 1 file changed"));
+    assert_eq!(lines.next(), Some(""));
+    assert_eq!(lines.next(), Some("- naïve.txt (+1/-0)"));
+}
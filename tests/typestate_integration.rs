@@ -26,7 +26,7 @@ fn typestate_flow_with_git() {
         .unwrap();
 
     let idle = VibeSession::<Idle>::new("integration-branch");
-    let vibing = idle.start();
+    let vibing = idle.start().unwrap();
 
     let branch = String::from_utf8(
         Command::new("git")
@@ -0,0 +1,33 @@
+use std::fs;
+use tempfile::tempdir;
+use vibe_git::McpClient;
+
+mod common;
+use common::{git, init_empty_repo};
+
+#[test]
+fn start_on_unborn_head_makes_first_commit_the_root() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path();
+    init_empty_repo(repo);
+
+    let mut client = McpClient::new().with_repo(repo);
+    client.start_vibing("unborn-branch").unwrap();
+    fs::write(repo.join("first.txt"), "hello\n").unwrap();
+    git(repo, &["add", "first.txt"]);
+    git(repo, &["commit", "-qm", "first"]);
+
+    assert_eq!(git(repo, &["branch", "--show-current"]), "unborn-branch");
+    let parents = git(repo, &["rev-list", "--parents", "-n", "1", "HEAD"]);
+    assert_eq!(
+        parents.split_whitespace().count(),
+        1,
+        "root commit has no parents"
+    );
+
+    // The base is still unborn, so stopping stays on the vibe branch.
+    let finished = client.stop_vibing().unwrap().unwrap();
+    assert!(finished.base_missing());
+    assert!(client.branch().is_none());
+    assert_eq!(git(repo, &["branch", "--show-current"]), "unborn-branch");
+}
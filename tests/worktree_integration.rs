@@ -0,0 +1,41 @@
+use tempfile::tempdir;
+use vibe_git::{Idle, McpClient, VibeError, VibeSession};
+
+mod common;
+use common::{git, init_repo};
+
+#[test]
+fn stop_stays_put_when_base_is_checked_out_in_another_worktree() {
+    let dir = tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    let linked = dir.path().join("linked");
+    std::fs::create_dir(&repo).unwrap();
+    init_repo(&repo);
+
+    let mut client = McpClient::new().with_repo(&repo);
+    client.start_vibing("worktree-vibe").unwrap();
+    git(
+        &repo,
+        &["worktree", "add", "-q", linked.to_str().unwrap(), "main"],
+    );
+
+    let finished = client.stop_vibing().unwrap().unwrap();
+    let worktree = finished.base_worktree().unwrap();
+    assert_eq!(
+        worktree.canonicalize().unwrap(),
+        linked.canonicalize().unwrap()
+    );
+    assert_eq!(git(&repo, &["branch", "--show-current"]), "worktree-vibe");
+    assert_eq!(git(&linked, &["branch", "--show-current"]), "main");
+
+    let session = VibeSession::<Idle>::in_repo(&repo, "delete-vibe")
+        .with_base("main")
+        .start()
+        .unwrap();
+    let result = session.finish_and_delete(true);
+    assert!(matches!(
+        result,
+        Err((_, VibeError::BaseCheckedOutElsewhere { ref base, .. })) if base == "main"
+    ));
+    assert_eq!(git(&repo, &["branch", "--show-current"]), "delete-vibe");
+}